@@ -1,19 +1,40 @@
-import type { ICommand, IRepoKitCommand } from "./types";
+import type {
+  ICommand,
+  IEnvironmentVariable,
+  OwnerReference,
+  IRepoKitCommand,
+  IShell,
+} from "./types";
 
 export class RepoKitCommand {
   name: string;
-  owner: string;
+  owner: OwnerReference | OwnerReference[];
   description: string;
   commands: Record<string, ICommand>;
+  env?: Record<string, string | IEnvironmentVariable>;
+  envFile?: string | string[];
+  cwd?: string;
+  shell?: string | IShell;
+  tags?: string[];
   constructor({
     name,
     description,
     owner = "",
     commands = {},
+    env,
+    envFile,
+    cwd,
+    shell,
+    tags,
   }: IRepoKitCommand) {
     this.name = name;
     this.owner = owner;
     this.commands = commands;
     this.description = description;
+    this.env = env;
+    this.envFile = envFile;
+    this.cwd = cwd;
+    this.shell = shell;
+    this.tags = tags;
   }
 }
@@ -1,4 +1,10 @@
-import type { ICommand, IRepoKitConfig } from "./types";
+import type {
+  ICommand,
+  IHistorySettings,
+  IOwner,
+  IRepoKitConfig,
+  IShell,
+} from "./types";
 import { RepoKitCommand } from "./RepoKitCommand";
 /* eslint-disable typescript-eslint(no-misused-spread */
 
@@ -6,9 +12,25 @@ export class RepoKitConfig implements Required<IRepoKitConfig> {
   project: string;
   thirdParty: RepoKitCommand[];
   commands: Record<string, ICommand>;
-  constructor({ project, commands = {}, thirdParty = [] }: IRepoKitConfig) {
+  autoRunSuggestions: boolean;
+  owners: Record<string, IOwner>;
+  history: IHistorySettings;
+  shell: string | IShell;
+  constructor({
+    project,
+    commands = {},
+    thirdParty = [],
+    autoRunSuggestions = false,
+    owners = {},
+    history = {},
+    shell = {},
+  }: IRepoKitConfig) {
     this.project = project;
     this.commands = commands;
+    this.autoRunSuggestions = autoRunSuggestions;
+    this.owners = owners;
+    this.history = history;
+    this.shell = shell;
     this.thirdParty = thirdParty.map(command => new RepoKitCommand(command));
   }
 
@@ -4,19 +4,82 @@ export interface IRepoKitConfig {
   project: string;
   thirdParty?: RepoKitCommand[];
   commands?: Record<string, ICommand>;
+  autoRunSuggestions?: boolean;
+  owners?: Record<string, IOwner>;
+  history?: IHistorySettings;
+  shell?: string | IShell;
+}
+
+export interface IShell {
+  program?: string;
+  flags?: string[];
+  strict?: boolean;
+}
+
+export interface IHistorySettings {
+  enabled?: boolean;
+  redact?: string[];
+  maxEntries?: number;
 }
 
 export interface IRepoKitCommand {
   name: string;
-  owner?: string;
+  owner?: OwnerReference | OwnerReference[];
   description: string;
   commands: Record<string, ICommand>;
+  env?: Record<string, string | IEnvironmentVariable>;
+  envFile?: string | string[];
+  cwd?: string;
+  shell?: string | IShell;
+  tags?: string[];
 }
 
 export interface ICommand {
-  command: string;
+  command?: string | string[];
+  script?: string;
+  interpreter?: string;
+  file?: string;
+  description: string;
+  timeout?: number | string;
+  retries?: number;
+  retryDelay?: number | string;
+  backoff?: number;
+  args?: Record<string, string | IArgument>;
+  env?: Record<string, string | IEnvironmentVariable>;
+  envFile?: string | string[];
+  cwd?: string;
+  shell?: string | IShell;
+  tags?: string[];
+}
+
+export type ArgumentType = "string" | "int" | "bool" | "enum" | "path";
+
+export interface IArgument {
+  description: string;
+  kind?: "flag" | "positional";
+  aliases?: string[];
+  required?: boolean;
+  default?: string | number | boolean;
+  type?: ArgumentType;
+  values?: string[];
+  repeats?: boolean;
+}
+
+export interface IEnvironmentVariable {
   description: string;
-  args?: Record<string, string>;
+  required?: boolean;
+  default?: string | number | boolean;
+  values?: string[];
+}
+
+export type OwnerReference = string | IOwner;
+
+export interface IOwner {
+  team?: string;
+  individuals?: string[];
+  channel?: string;
+  email?: string;
+  onCall?: string;
 }
 
 export interface ILocatedCommand extends IRepoKitCommand {
@@ -0,0 +1,255 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+/// The locations GitHub searches for a CODEOWNERS file, in order
+static LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug, Clone)]
+pub struct OwnershipRule {
+    pub pattern: String,
+    pub owners: Vec<String>,
+    pub line: usize,
+    regex: Regex,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodeOwners {
+    pub path: PathBuf,
+    pub rules: Vec<OwnershipRule>,
+}
+
+impl CodeOwners {
+    /// Parses the first CODEOWNERS file found beneath the root
+    pub fn load(root: &str) -> Option<CodeOwners> {
+        let path = LOCATIONS
+            .iter()
+            .map(|location| Path::new(root).join(location))
+            .find(|path| path.is_file())?;
+        let contents = fs::read_to_string(&path).ok()?;
+        Some(CodeOwners {
+            path,
+            rules: CodeOwners::parse(&contents),
+        })
+    }
+
+    pub fn locations() -> String {
+        LOCATIONS.join(" | ")
+    }
+
+    pub fn parse(contents: &str) -> Vec<OwnershipRule> {
+        contents
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let mut tokens = line
+                    .split_whitespace()
+                    .take_while(|token| !token.starts_with('#'));
+                let pattern = tokens.next()?.to_string();
+                Some(OwnershipRule {
+                    regex: CodeOwners::compile(&pattern)?,
+                    owners: tokens.map(String::from).collect(),
+                    line: index + 1,
+                    pattern,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the last rule matching a path relative to the root.
+    /// Rules without owners leave the path unowned
+    pub fn rule_for(&self, path: &str) -> Option<&OwnershipRule> {
+        let path = path.trim_start_matches('/');
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.regex.is_match(path))
+            .filter(|rule| !rule.owners.is_empty())
+    }
+
+    /// Whether a free-text owner refers to one of the CODEOWNERS owners.
+    /// "Platform Team" agrees with @org/platform-team
+    pub fn agrees(declared: &str, owners: &[String]) -> bool {
+        let declared = CodeOwners::normalize(declared);
+        owners.iter().any(|owner| {
+            let owner = CodeOwners::normalize(owner);
+            let team = owner.rsplit('/').next().unwrap_or(&owner);
+            declared == owner || declared == team
+        })
+    }
+
+    fn normalize(owner: &str) -> String {
+        owner
+            .trim()
+            .trim_start_matches('@')
+            .to_lowercase()
+            .split(|c: char| c.is_whitespace() || c == '_')
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<&str>>()
+            .join("-")
+    }
+
+    /// Converts a gitignore style pattern into a regular expression
+    fn compile(pattern: &str) -> Option<Regex> {
+        let anchored = pattern.starts_with('/') || pattern.trim_end_matches('/').contains('/');
+        let directory = pattern.ends_with('/');
+        let glob = pattern.trim_start_matches('/').trim_end_matches('/');
+        let characters: Vec<char> = glob.chars().collect();
+        let mut expression = String::from(match anchored {
+            true => "^",
+            false => "^(?:.*/)?",
+        });
+        let mut index = 0;
+        while index < characters.len() {
+            let character = characters[index];
+            let double = character == '*' && characters.get(index + 1) == Some(&'*');
+            match character {
+                '*' if double && characters.get(index + 2) == Some(&'/') => {
+                    expression.push_str("(?:.*/)?");
+                    index += 2;
+                }
+                '*' if double => {
+                    expression.push_str(".*");
+                    index += 1;
+                }
+                '*' => expression.push_str("[^/]*"),
+                '?' => expression.push_str("[^/]"),
+                _ => expression.push_str(&regex::escape(&character.to_string())),
+            }
+            index += 1;
+        }
+        let shallow = glob.ends_with("/*") && !glob.ends_with("**/*");
+        expression.push_str(match (directory, shallow) {
+            (true, _) => "/.*$",
+            (false, true) => "$",
+            (false, false) => "(?:/.*)?$",
+        });
+        Regex::new(&expression).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::CodeOwners;
+
+    fn owners(contents: &str) -> CodeOwners {
+        CodeOwners {
+            path: PathBuf::from("CODEOWNERS"),
+            rules: CodeOwners::parse(contents),
+        }
+    }
+
+    fn owner_of(code_owners: &CodeOwners, path: &str) -> Option<String> {
+        code_owners.rule_for(path).map(|rule| rule.owners.join(" "))
+    }
+
+    #[test]
+    fn parses_rules_and_comments() {
+        let rules =
+            CodeOwners::parse("# comment\n\n*.rs @rust # trailing\n/docs/ @docs @writers\n");
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].pattern, "*.rs");
+        assert_eq!(rules[0].owners, ["@rust"]);
+        assert_eq!(rules[0].line, 3);
+        assert_eq!(rules[1].owners, ["@docs", "@writers"]);
+    }
+
+    #[test]
+    fn matches_unanchored_patterns_at_any_depth() {
+        let code_owners = owners("*.rs @rust\nbuild @build\n");
+        assert_eq!(
+            owner_of(&code_owners, "src/main.rs").as_deref(),
+            Some("@rust")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "build/out.txt").as_deref(),
+            Some("@build")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "apps/build/x").as_deref(),
+            Some("@build")
+        );
+        assert_eq!(owner_of(&code_owners, "src/main.ts"), None);
+    }
+
+    #[test]
+    fn anchors_patterns_with_a_leading_slash() {
+        let code_owners = owners("/apps @apps\n");
+        assert_eq!(
+            owner_of(&code_owners, "apps/web/package.json").as_deref(),
+            Some("@apps")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "/apps/web").as_deref(),
+            Some("@apps")
+        );
+        assert_eq!(owner_of(&code_owners, "libs/apps/x"), None);
+    }
+
+    #[test]
+    fn matches_double_star_globs() {
+        let code_owners = owners("services/**/api @api\n**/fixtures/ @qa\ndocs/** @docs\n");
+        assert_eq!(
+            owner_of(&code_owners, "services/api/x.go").as_deref(),
+            Some("@api")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "services/a/b/api/x.go").as_deref(),
+            Some("@api")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "a/b/fixtures/one.json").as_deref(),
+            Some("@qa")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "docs/guides/intro.md").as_deref(),
+            Some("@docs")
+        );
+        assert_eq!(owner_of(&code_owners, "other/api/x.go"), None);
+    }
+
+    #[test]
+    fn matches_single_level_wildcards() {
+        let code_owners = owners("docs/* @docs\n");
+        assert_eq!(
+            owner_of(&code_owners, "docs/readme.md").as_deref(),
+            Some("@docs")
+        );
+        assert_eq!(owner_of(&code_owners, "docs/nested/readme.md"), None);
+    }
+
+    #[test]
+    fn lets_the_last_matching_rule_win() {
+        let code_owners = owners("* @everyone\n/apps/ @apps\n/apps/web/ @web\n/apps/web/vendor/\n");
+        assert_eq!(
+            owner_of(&code_owners, "README.md").as_deref(),
+            Some("@everyone")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "apps/api/main.go").as_deref(),
+            Some("@apps")
+        );
+        assert_eq!(
+            owner_of(&code_owners, "apps/web/index.ts").as_deref(),
+            Some("@web")
+        );
+        assert_eq!(owner_of(&code_owners, "apps/web/vendor/lib.js"), None);
+    }
+
+    #[test]
+    fn agrees_with_free_text_team_names() {
+        let owners = ["@acme/platform-team".to_string()];
+        assert!(CodeOwners::agrees("Platform Team", &owners));
+        assert!(CodeOwners::agrees("@acme/platform-team", &owners));
+        assert!(!CodeOwners::agrees("Platform", &owners));
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    internal_filesystem::internal_filesystem::InternalFileSystem,
+    repokit::interfaces::RepoKitCommand, static_parser::static_parser::ParseStrategy,
+};
+
+static CACHE_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedManifest {
+    pub modified: u64,
+    pub size: u64,
+    pub hash: String,
+    pub parser: ParseStrategy,
+    pub commands: Vec<RepoKitCommand>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct CacheFile {
+    version: String,
+    manifests: HashMap<String, CachedManifest>,
+}
+
+pub enum ManifestStatus {
+    Fresh,
+    Stale,
+    Missing,
+}
+
+pub struct CommandCache {
+    root: String,
+    path: PathBuf,
+    manifests: HashMap<String, CachedManifest>,
+}
+
+impl CommandCache {
+    pub fn new(root: &str) -> CommandCache {
+        let path = InternalFileSystem::new(root)
+            .repokit_directory()
+            .join("cache.json");
+        let manifests = CommandCache::load(&path);
+        CommandCache {
+            root: root.to_string(),
+            path,
+            manifests,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn manifests(&self) -> &HashMap<String, CachedManifest> {
+        &self.manifests
+    }
+
+    pub fn is_fresh(&self, manifest: &str) -> bool {
+        matches!(self.status(manifest), ManifestStatus::Fresh)
+    }
+
+    pub fn status(&self, manifest: &str) -> ManifestStatus {
+        let Some(cached) = self.manifests.get(manifest) else {
+            return ManifestStatus::Missing;
+        };
+        let Some((modified, size)) = self.metadata(manifest) else {
+            return ManifestStatus::Stale;
+        };
+        if cached.modified == modified && cached.size == size {
+            return ManifestStatus::Fresh;
+        }
+        match self.hash(manifest) {
+            Some(hash) if hash == cached.hash => ManifestStatus::Fresh,
+            _ => ManifestStatus::Stale,
+        }
+    }
+
+    pub fn partition(&self, manifests: &[String]) -> (Vec<RepoKitCommand>, Vec<String>) {
+        let mut commands: Vec<RepoKitCommand> = Vec::new();
+        let mut stale: Vec<String> = Vec::new();
+        for manifest in manifests {
+            if self.is_fresh(manifest) {
+                let cached = self.manifests.get(manifest).expect("exists");
+                commands.extend(cached.commands.iter().cloned());
+            } else {
+                stale.push(manifest.clone());
+            }
+        }
+        (commands, stale)
+    }
+
+    pub fn store(&mut self, manifest: &str, parser: ParseStrategy, commands: Vec<RepoKitCommand>) {
+        let Some((modified, size)) = self.metadata(manifest) else {
+            return;
+        };
+        let hash = self.hash(manifest).unwrap_or_default();
+        self.manifests.insert(
+            manifest.to_string(),
+            CachedManifest {
+                modified,
+                size,
+                hash,
+                parser,
+                commands,
+            },
+        );
+    }
+
+    pub fn retain(&mut self, manifests: &[String]) {
+        self.manifests
+            .retain(|manifest, _| manifests.contains(manifest));
+    }
+
+    pub fn save(&self) {
+        let file = CacheFile {
+            version: CACHE_VERSION.to_string(),
+            manifests: self.manifests.clone(),
+        };
+        let Some(directory) = self.path.parent() else {
+            return;
+        };
+        if fs::create_dir_all(directory).is_err() {
+            return;
+        }
+        let temporary = self.path.with_extension("json.tmp");
+        if let Ok(contents) = serde_json::to_string(&file)
+            && fs::write(&temporary, contents).is_ok()
+        {
+            let _ = fs::rename(&temporary, &self.path);
+        }
+    }
+
+    pub fn clear(&mut self) -> bool {
+        self.manifests.clear();
+        fs::remove_file(&self.path).is_ok()
+    }
+
+    fn load(path: &Path) -> HashMap<String, CachedManifest> {
+        let Ok(contents) = fs::read_to_string(path) else {
+            return HashMap::new();
+        };
+        match serde_json::from_str::<CacheFile>(&contents) {
+            Ok(file) if file.version == CACHE_VERSION => file.manifests,
+            _ => HashMap::new(),
+        }
+    }
+
+    fn absolute(&self, manifest: &str) -> String {
+        format!("{}{}", self.root, manifest)
+    }
+
+    fn metadata(&self, manifest: &str) -> Option<(u64, u64)> {
+        let metadata = fs::metadata(self.absolute(manifest)).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos() as u64;
+        Some((modified, metadata.len()))
+    }
+
+    fn hash(&self, manifest: &str) -> Option<String> {
+        let contents = fs::read(self.absolute(manifest)).ok()?;
+        Some(CommandCache::fnv1a(&contents))
+    }
+
+    fn fnv1a(bytes: &[u8]) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in bytes {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{hash:016x}")
+    }
+}
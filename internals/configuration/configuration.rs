@@ -1,14 +1,72 @@
-use std::{fs::File, io, path::Path, process::exit};
+use std::{
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+    process::exit,
+};
 
-use crate::{internal_filesystem::internal_filesystem::InternalFileSystem, logger::logger::Logger};
+use crate::{
+    internal_commands::typescript_command::TypescriptCommand,
+    internal_filesystem::internal_filesystem::InternalFileSystem,
+    logger::logger::Logger,
+    repokit::interfaces::RepoKitConfig,
+    static_parser::{
+        data_parser::{DATA_MANIFESTS, DataParser},
+        static_parser::{ParseStrategy, StaticParser},
+    },
+    validations::command_validations::CommandValidations,
+};
 
 pub struct Configuration;
 
 impl Configuration {
+    pub fn load(root: &str) -> RepoKitConfig {
+        Configuration::create(root);
+        let path = Configuration::path(root);
+        let config = if let Some(strategy) = DataParser::strategy(&path) {
+            match DataParser::parse_configuration(&path) {
+                Ok(config) => RepoKitConfig { strategy, ..config },
+                Err(error) => DataParser::exit_on_failure(&path, &error),
+            }
+        } else {
+            match StaticParser::parse_configuration(&path) {
+                Ok(config) => config,
+                Err(_) => RepoKitConfig {
+                    strategy: ParseStrategy::Typescript,
+                    ..TypescriptCommand::new(root).parse_configuration()
+                },
+            }
+        };
+        CommandValidations::validate_definitions(&config.commands, root, &path.to_string_lossy());
+        config
+    }
+
+    pub fn path(root: &str) -> PathBuf {
+        let typescript = Path::new(root).join("repokit.ts");
+        if typescript.exists() {
+            return typescript;
+        }
+        for file_name in DATA_MANIFESTS {
+            let path = Path::new(root).join(file_name);
+            if path.exists() {
+                return path;
+            }
+        }
+        typescript
+    }
+
+    pub fn file_name(root: &str) -> String {
+        Configuration::path(root)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("repokit.ts")
+            .to_string()
+    }
+
     pub fn create(root: &str) {
         let file_path = format!("{root}/repokit.ts");
         let path_buf = Path::new(&file_path);
-        if path_buf.exists() {
+        if Configuration::path(root).exists() {
             return;
         }
         Configuration::welcome();
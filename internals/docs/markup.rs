@@ -0,0 +1,230 @@
+static STYLESHEET: &str = "body{font-family:-apple-system,BlinkMacSystemFont,\"Segoe UI\",sans-serif;line-height:1.5;margin:0 auto;max-width:960px;padding:0 24px 48px;color:#1f2328}a{color:#0969da;text-decoration:none}a:hover{text-decoration:underline}code,pre{font-family:ui-monospace,Menlo,monospace;background:#f6f8fa;border-radius:6px}code{padding:2px 4px}pre{padding:12px;overflow:auto}table{border-collapse:collapse;margin:8px 0}th,td{border:1px solid #d0d7de;padding:6px 12px;text-align:left;vertical-align:top}input{font-size:16px;padding:8px;width:100%;box-sizing:border-box}";
+
+static SEARCH_SCRIPT: &str = "const input=document.getElementById(\"search\");const results=document.getElementById(\"results\");input.addEventListener(\"input\",()=>{const query=input.value.trim().toLowerCase();results.innerHTML=\"\";if(!query)return;for(const entry of SEARCH_INDEX){const text=[entry.name,entry.description,entry.owner||\"\",entry.location||\"\",entry.tags.join(\" \")].join(\" \").toLowerCase();if(!text.includes(query))continue;const item=document.createElement(\"li\");const link=document.createElement(\"a\");link.href=entry.url;link.textContent=entry.name;item.append(link,\" \",entry.description);results.append(item)}});";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocsFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Debug, Clone)]
+pub enum Inline {
+    Text(String),
+    Strong(String),
+    Code(String),
+    Link(String, String),
+}
+
+pub type Line = Vec<Inline>;
+
+#[derive(Debug, Clone)]
+pub enum Block {
+    Heading(usize, String),
+    Paragraph(Line),
+    List(Vec<Line>),
+    Code(String),
+    Table(Vec<String>, Vec<Vec<Line>>),
+    /// A search box over the serialized index. Only rendered in HTML
+    Search(String),
+}
+
+pub struct Page {
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+impl DocsFormat {
+    pub fn parse(value: &str) -> Result<DocsFormat, String> {
+        match value.to_lowercase().as_str() {
+            "md" | "markdown" => Ok(DocsFormat::Markdown),
+            "html" => Ok(DocsFormat::Html),
+            _ => Err(format!(
+                "The option --format expects one of md | html but received \"{value}\""
+            )),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocsFormat::Markdown => "md",
+            DocsFormat::Html => "html",
+        }
+    }
+}
+
+impl Page {
+    pub fn new(title: &str) -> Page {
+        Page {
+            title: title.to_string(),
+            blocks: vec![Block::Heading(1, title.to_string())],
+        }
+    }
+
+    pub fn push(&mut self, block: Block) {
+        self.blocks.push(block);
+    }
+
+    pub fn render(&self, format: DocsFormat) -> String {
+        match format {
+            DocsFormat::Markdown => self.markdown(),
+            DocsFormat::Html => self.html(),
+        }
+    }
+
+    /// Converts heading text into the anchor that both GitHub flavored
+    /// markdown and the HTML renderer assign to it
+    pub fn anchor(text: &str) -> String {
+        text.to_lowercase()
+            .chars()
+            .filter_map(|character| match character {
+                ' ' => Some('-'),
+                c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn markdown(&self) -> String {
+        let blocks: Vec<String> = self
+            .blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Heading(level, text) => Some(format!(
+                    "{} {}",
+                    "#".repeat(*level),
+                    Page::escape_markdown(text)
+                )),
+                Block::Paragraph(line) => Some(Page::markdown_line(line)),
+                Block::List(items) => Some(
+                    items
+                        .iter()
+                        .map(|item| format!("- {}", Page::markdown_line(item)))
+                        .collect::<Vec<String>>()
+                        .join("\n"),
+                ),
+                Block::Code(code) => {
+                    let fence = if code.contains("```") { "~~~" } else { "```" };
+                    Some(format!("{fence}\n{code}\n{fence}"))
+                }
+                Block::Table(headers, rows) => {
+                    let mut lines = vec![
+                        format!("| {} |", headers.join(" | ")),
+                        format!("|{}", " --- |".repeat(headers.len())),
+                    ];
+                    for row in rows {
+                        let cells: Vec<String> = row
+                            .iter()
+                            .map(|cell| Page::markdown_line(cell).replace('|', "\\|"))
+                            .collect();
+                        lines.push(format!("| {} |", cells.join(" | ")));
+                    }
+                    Some(lines.join("\n"))
+                }
+                Block::Search(_) => None,
+            })
+            .collect();
+        blocks.join("\n\n") + "\n"
+    }
+
+    fn markdown_line(line: &Line) -> String {
+        line.iter()
+            .map(|inline| match inline {
+                Inline::Text(text) => Page::escape_markdown(text),
+                Inline::Strong(text) => format!("**{}**", Page::escape_markdown(text)),
+                Inline::Code(code) if code.contains('`') => format!("`` {code} ``"),
+                Inline::Code(code) => format!("`{code}`"),
+                Inline::Link(text, href) => format!(
+                    "[{}]({})",
+                    Page::escape_markdown(text),
+                    href.replace(' ', "%20")
+                ),
+            })
+            .collect::<Vec<String>>()
+            .join("")
+            .replace('\n', " ")
+    }
+
+    fn escape_markdown(text: &str) -> String {
+        let mut escaped = String::new();
+        for character in text.chars() {
+            if matches!(character, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>') {
+                escaped.push('\\');
+            }
+            escaped.push(character);
+        }
+        escaped
+    }
+
+    fn html(&self) -> String {
+        let body: Vec<String> = self
+            .blocks
+            .iter()
+            .map(|block| match block {
+                Block::Heading(level, text) => format!(
+                    "<h{level} id=\"{}\">{}</h{level}>",
+                    Page::anchor(text),
+                    Page::escape_html(text)
+                ),
+                Block::Paragraph(line) => format!("<p>{}</p>", Page::html_line(line)),
+                Block::List(items) => format!(
+                    "<ul>{}</ul>",
+                    items
+                        .iter()
+                        .map(|item| format!("<li>{}</li>", Page::html_line(item)))
+                        .collect::<String>()
+                ),
+                Block::Code(code) => format!("<pre><code>{}</code></pre>", Page::escape_html(code)),
+                Block::Table(headers, rows) => {
+                    let headers: String = headers
+                        .iter()
+                        .map(|header| format!("<th>{}</th>", Page::escape_html(header)))
+                        .collect();
+                    let rows: String = rows
+                        .iter()
+                        .map(|row| {
+                            let cells: String = row
+                                .iter()
+                                .map(|cell| format!("<td>{}</td>", Page::html_line(cell)))
+                                .collect();
+                            format!("<tr>{cells}</tr>")
+                        })
+                        .collect();
+                    format!("<table><thead><tr>{headers}</tr></thead><tbody>{rows}</tbody></table>")
+                }
+                Block::Search(index) => format!(
+                    "<input id=\"search\" type=\"search\" placeholder=\"Search commands\" autofocus><ul id=\"results\"></ul><script>const SEARCH_INDEX={};{SEARCH_SCRIPT}</script>",
+                    index.replace("</", "<\\/")
+                ),
+            })
+            .collect();
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>{STYLESHEET}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+            Page::escape_html(&self.title),
+            body.join("\n")
+        )
+    }
+
+    fn html_line(line: &Line) -> String {
+        line.iter()
+            .map(|inline| match inline {
+                Inline::Text(text) => Page::escape_html(text),
+                Inline::Strong(text) => format!("<strong>{}</strong>", Page::escape_html(text)),
+                Inline::Code(code) => format!("<code>{}</code>", Page::escape_html(code)),
+                Inline::Link(text, href) => format!(
+                    "<a href=\"{}\">{}</a>",
+                    Page::escape_html(href),
+                    Page::escape_html(text)
+                ),
+            })
+            .collect()
+    }
+
+    fn escape_html(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    }
+}
@@ -0,0 +1,510 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    io::{self},
+    path::{Component, Path, PathBuf},
+};
+
+use alphanumeric_sort::sort_slice_by_str_key;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::{
+    configuration::configuration::Configuration,
+    docs::markup::{Block, DocsFormat, Inline, Line, Page},
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{InternalExecutableDefinition, RepoKitScope},
+    },
+    internal_commands::help::Help,
+    repokit::interfaces::{
+        ArgumentDefinition, CommandDefinition, Environment, EnvironmentDefinition, Owner,
+        OwnerDetails, RepoKitCommand,
+    },
+    search::search_document::{SearchDocument, SearchSource},
+    validations::argument_validations::ArgumentValidations,
+};
+
+static UNOWNED: &str = "Unowned";
+
+pub struct Site<'a> {
+    scope: &'a RepoKitScope,
+    format: DocsFormat,
+    out: PathBuf,
+    internals: Vec<&'a InternalExecutableDefinition>,
+    packages: Vec<&'a RepoKitCommand>,
+    package_files: HashMap<String, String>,
+    documents: Vec<SearchDocument>,
+}
+
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    name: &'a str,
+    source: SearchSource,
+    description: &'a str,
+    owner: &'a Option<String>,
+    location: &'a Option<String>,
+    tags: &'a [String],
+    url: String,
+}
+
+impl<'a> Site<'a> {
+    pub fn new(
+        scope: &'a RepoKitScope,
+        format: DocsFormat,
+        out: &Path,
+        internals: &'a HashMap<String, Box<dyn InternalExecutable>>,
+        externals: &'a HashMap<String, RepoKitCommand>,
+    ) -> Site<'a> {
+        let mut definitions: Vec<&InternalExecutableDefinition> =
+            internals.values().map(|x| x.get_definition()).collect();
+        sort_slice_by_str_key(&mut definitions, |x| &x.name);
+        let mut packages: Vec<&RepoKitCommand> = externals.values().collect();
+        sort_slice_by_str_key(&mut packages, |x| &x.name);
+        let mut documents = SearchDocument::collect(scope, internals, externals);
+        documents.sort_by(|a, b| a.name.cmp(&b.name));
+        Site {
+            scope,
+            format,
+            out: out.to_path_buf(),
+            internals: definitions,
+            package_files: Site::package_files(&packages),
+            packages,
+            documents,
+        }
+    }
+
+    /// Names each package's page after its anchor. Packages whose names
+    /// share an anchor, such as @scope/ui and scope-ui, are numbered in
+    /// order so that no page overwrites another
+    fn package_files(packages: &[&RepoKitCommand]) -> HashMap<String, String> {
+        let mut files = HashMap::new();
+        let mut taken = HashSet::new();
+        for package in packages {
+            let anchor = match Page::anchor(&package.name) {
+                anchor if anchor.is_empty() => "package".to_string(),
+                anchor => anchor,
+            };
+            let mut file = anchor.clone();
+            let mut suffix = 2;
+            while !taken.insert(file.clone()) {
+                file = format!("{anchor}-{suffix}");
+                suffix += 1;
+            }
+            files.insert(package.name.clone(), file);
+        }
+        files
+    }
+
+    /// Writes every page and the search index, returning the number of
+    /// pages written
+    pub fn write(&self) -> io::Result<usize> {
+        let mut pages = vec![
+            (self.file("index"), self.index_page()?),
+            (self.file("root"), self.root_page()),
+            (self.file("internal"), self.internal_page()),
+            (self.file("owners"), self.owners_page()),
+            (self.file("directories"), self.directories_page()),
+        ];
+        for package in &self.packages {
+            pages.push((self.package_file(package), self.package_page(package)));
+        }
+        fs::create_dir_all(self.out.join("packages"))?;
+        for (path, page) in &pages {
+            fs::write(self.out.join(path), page.render(self.format))?;
+        }
+        fs::write(
+            self.out.join("search-index.json"),
+            self.search_index()? + "\n",
+        )?;
+        Ok(pages.len())
+    }
+
+    fn index_page(&self) -> io::Result<Page> {
+        let file = self.file("index");
+        let mut page = Page::new(&self.scope.configuration.project);
+        page.push(self.navigation(&file));
+        page.push(Block::Paragraph(vec![Inline::Text(format!(
+            "A catalog of {} commands across {} packages",
+            self.documents.len(),
+            self.packages.len()
+        ))]));
+        page.push(Block::Search(self.search_index()?));
+        page.push(Block::Heading(2, "Packages".to_string()));
+        page.push(self.package_list(&file, &self.packages));
+        Ok(page)
+    }
+
+    fn root_page(&self) -> Page {
+        let file = self.file("root");
+        let mut page = Page::new("Root Commands");
+        page.push(self.navigation(&file));
+        page.push(Block::Paragraph(
+            [
+                vec![
+                    Inline::Strong("Defined in:".to_string()),
+                    Inline::Text(" ".to_string()),
+                ],
+                self.manifest_link(&file, &Configuration::path(&self.scope.root)),
+            ]
+            .concat(),
+        ));
+        let commands = &self.scope.configuration.commands;
+        let mut names: Vec<&String> = commands.keys().collect();
+        names.sort();
+        for name in names {
+            page.push(Block::Heading(2, name.clone()));
+            page.blocks.extend(Site::command_blocks(&commands[name]));
+        }
+        page
+    }
+
+    fn internal_page(&self) -> Page {
+        let file = self.file("internal");
+        let mut page = Page::new("Internal Commands");
+        page.push(self.navigation(&file));
+        for definition in &self.internals {
+            page.push(Block::Heading(2, definition.name.clone()));
+            page.push(Block::Paragraph(vec![Inline::Text(Site::plain(
+                &definition.description,
+            ))]));
+            let mut args: Vec<(&String, &String)> =
+                definition.args.iter().flat_map(|x| x.iter()).collect();
+            args.sort();
+            if !args.is_empty() {
+                let rows = args
+                    .into_iter()
+                    .map(|(name, description)| {
+                        vec![
+                            vec![Inline::Code(name.clone())],
+                            vec![Inline::Text(Site::plain(description))],
+                        ]
+                    })
+                    .collect();
+                page.push(Site::table("Argument", rows));
+            }
+        }
+        page
+    }
+
+    fn owners_page(&self) -> Page {
+        let file = self.file("owners");
+        let mut page = Page::new("Owners");
+        page.push(self.navigation(&file));
+        let mut owners: BTreeMap<&str, (Option<&OwnerDetails>, Vec<&RepoKitCommand>)> =
+            BTreeMap::new();
+        for package in &self.packages {
+            for owner in package.owner.all() {
+                let (details, packages) = owners.entry(owner.name()).or_default();
+                if let Owner::Details(declared) = owner {
+                    details.get_or_insert(declared);
+                }
+                packages.push(package);
+            }
+        }
+        let unowned: Vec<&RepoKitCommand> = self
+            .packages
+            .iter()
+            .filter(|x| x.owner.is_empty())
+            .copied()
+            .collect();
+        for (owner, (details, packages)) in owners {
+            page.push(Block::Heading(2, owner.to_string()));
+            if let Some(details) = details {
+                page.blocks.extend(Site::contacts(details));
+            }
+            page.push(self.package_list(&file, &packages));
+        }
+        if !unowned.is_empty() {
+            page.push(Block::Heading(2, UNOWNED.to_string()));
+            page.push(self.package_list(&file, &unowned));
+        }
+        page
+    }
+
+    fn directories_page(&self) -> Page {
+        let file = self.file("directories");
+        let mut page = Page::new("Directories");
+        page.push(self.navigation(&file));
+        let mut directories: BTreeMap<String, Vec<&RepoKitCommand>> = BTreeMap::new();
+        for package in &self.packages {
+            let location = self.relative_to_root(&package.location);
+            let directory = Path::new(&location)
+                .parent()
+                .map(|x| x.to_string_lossy().to_string())
+                .filter(|x| !x.is_empty())
+                .unwrap_or(".".to_string());
+            directories.entry(directory).or_default().push(package);
+        }
+        for (directory, packages) in directories {
+            page.push(Block::Heading(2, directory));
+            page.push(self.package_list(&file, &packages));
+        }
+        page
+    }
+
+    fn package_page(&self, package: &RepoKitCommand) -> Page {
+        let file = self.package_file(package);
+        let mut page = Page::new(&package.name);
+        page.push(self.navigation(&file));
+        page.push(Block::Paragraph(vec![Inline::Text(
+            package.description.clone(),
+        )]));
+        let mut owners = package.owner.names();
+        if owners.is_empty() {
+            owners.push(UNOWNED);
+        }
+        let mut line = vec![
+            Inline::Strong("Owned by:".to_string()),
+            Inline::Text(" ".to_string()),
+        ];
+        for (index, owner) in owners.into_iter().enumerate() {
+            if index > 0 {
+                line.push(Inline::Text(", ".to_string()));
+            }
+            line.push(Inline::Link(
+                owner.to_string(),
+                format!(
+                    "{}#{}",
+                    self.link(&file, &self.file("owners")),
+                    Page::anchor(owner)
+                ),
+            ));
+        }
+        page.push(Block::Paragraph(line));
+        page.push(Block::Paragraph(
+            [
+                vec![
+                    Inline::Strong("Defined in:".to_string()),
+                    Inline::Text(" ".to_string()),
+                ],
+                self.manifest_link(&file, Path::new(&package.location)),
+            ]
+            .concat(),
+        ));
+        page.blocks.extend(Site::tags(&package.tags));
+        page.blocks.extend(Site::environment(&package.env));
+        let mut names: Vec<&String> = package.commands.keys().collect();
+        names.sort();
+        for name in names {
+            page.push(Block::Heading(2, format!("{} {}", package.name, name)));
+            page.blocks
+                .extend(Site::command_blocks(&package.commands[name]));
+        }
+        page
+    }
+
+    fn command_blocks(definition: &CommandDefinition) -> Vec<Block> {
+        let mut blocks = vec![
+            Block::Paragraph(vec![Inline::Text(definition.description.clone())]),
+            Block::Code(definition.command_text()),
+        ];
+        if let Some(args) = &definition.args {
+            let rows = args
+                .iter()
+                .map(|(key, definition)| {
+                    let description = match definition {
+                        ArgumentDefinition::Description(description) => description.clone(),
+                        ArgumentDefinition::Schema(schema) => Help::describe_schema(schema),
+                    };
+                    vec![
+                        vec![Inline::Code(
+                            ArgumentValidations::names(key, definition).join(" | "),
+                        )],
+                        vec![Inline::Text(description)],
+                    ]
+                })
+                .collect();
+            blocks.push(Site::table("Argument", rows));
+        }
+        blocks.extend(Site::environment(&definition.env));
+        blocks.extend(Site::tags(&definition.tags));
+        blocks
+    }
+
+    fn environment(env: &Option<Environment>) -> Option<Block> {
+        let rows: Vec<Vec<Line>> = env
+            .iter()
+            .flat_map(|x| x.iter())
+            .map(|(name, definition)| {
+                let description = match definition {
+                    EnvironmentDefinition::Description(description) => description.clone(),
+                    EnvironmentDefinition::Schema(schema) => Help::describe_environment(schema),
+                };
+                vec![
+                    vec![Inline::Code(format!("${name}"))],
+                    vec![Inline::Text(description)],
+                ]
+            })
+            .collect();
+        (!rows.is_empty()).then(|| Site::table("Variable", rows))
+    }
+
+    fn contacts(details: &OwnerDetails) -> Option<Block> {
+        let individuals = details.individuals.join(", ");
+        let contacts = [
+            ("Individuals:", Some(&individuals).filter(|x| !x.is_empty())),
+            ("Channel:", details.channel.as_ref()),
+            ("Email:", details.email.as_ref()),
+            ("On-call:", details.on_call.as_ref()),
+        ];
+        let items: Vec<Line> = contacts
+            .into_iter()
+            .filter_map(|(label, value)| {
+                let value = value?;
+                let value = match value.starts_with("http") {
+                    true => Inline::Link(value.clone(), value.clone()),
+                    false => Inline::Text(value.clone()),
+                };
+                Some(vec![
+                    Inline::Strong(label.to_string()),
+                    Inline::Text(" ".to_string()),
+                    value,
+                ])
+            })
+            .collect();
+        (!items.is_empty()).then_some(Block::List(items))
+    }
+
+    fn tags(tags: &[String]) -> Option<Block> {
+        if tags.is_empty() {
+            return None;
+        }
+        let mut line = vec![
+            Inline::Strong("Tags:".to_string()),
+            Inline::Text(" ".to_string()),
+        ];
+        for (index, tag) in tags.iter().enumerate() {
+            if index > 0 {
+                line.push(Inline::Text(", ".to_string()));
+            }
+            line.push(Inline::Code(tag.clone()));
+        }
+        Some(Block::Paragraph(line))
+    }
+
+    fn table(label: &str, rows: Vec<Vec<Line>>) -> Block {
+        Block::Table(vec![label.to_string(), "Description".to_string()], rows)
+    }
+
+    fn navigation(&self, from: &str) -> Block {
+        let pages = [
+            ("Home", "index"),
+            ("Root Commands", "root"),
+            ("Internal Commands", "internal"),
+            ("Owners", "owners"),
+            ("Directories", "directories"),
+        ];
+        let mut line = Vec::new();
+        for (index, (label, name)) in pages.into_iter().enumerate() {
+            if index > 0 {
+                line.push(Inline::Text(" · ".to_string()));
+            }
+            line.push(Inline::Link(
+                label.to_string(),
+                self.link(from, &self.file(name)),
+            ));
+        }
+        Block::Paragraph(line)
+    }
+
+    fn package_list(&self, from: &str, packages: &[&RepoKitCommand]) -> Block {
+        Block::List(
+            packages
+                .iter()
+                .map(|package| {
+                    vec![
+                        Inline::Link(
+                            package.name.clone(),
+                            self.link(from, &self.package_file(package)),
+                        ),
+                        Inline::Text(format!(": {}", package.description)),
+                    ]
+                })
+                .collect(),
+        )
+    }
+
+    /// Links to a manifest, displaying its path relative to the root
+    fn manifest_link(&self, from: &str, manifest: &Path) -> Line {
+        let location = self.relative_to_root(manifest.to_str().unwrap_or_default());
+        let directory = self.out.join(from);
+        let directory = directory.parent().unwrap_or(&self.out);
+        vec![Inline::Link(location, Site::relative(directory, manifest))]
+    }
+
+    fn search_index(&self) -> io::Result<String> {
+        let entries: Vec<IndexEntry> = self
+            .documents
+            .iter()
+            .map(|document| IndexEntry {
+                name: &document.name,
+                source: document.source,
+                description: &document.description,
+                owner: &document.owner,
+                location: &document.location,
+                tags: &document.tags,
+                url: self.url(document),
+            })
+            .collect();
+        serde_json::to_string_pretty(&entries).map_err(io::Error::other)
+    }
+
+    /// The location of a command's documentation relative to the index
+    fn url(&self, document: &SearchDocument) -> String {
+        let page = match document.source {
+            SearchSource::Internal => self.file("internal"),
+            SearchSource::Root => self.file("root"),
+            SearchSource::Registered => self
+                .packages
+                .iter()
+                .find(|x| document.invocation.first() == Some(&x.name))
+                .map(|x| self.package_file(x))
+                .unwrap_or(self.file("index")),
+        };
+        format!("{page}#{}", Page::anchor(&document.name))
+    }
+
+    fn file(&self, name: &str) -> String {
+        format!("{name}.{}", self.format.extension())
+    }
+
+    fn package_file(&self, package: &RepoKitCommand) -> String {
+        let name = &self.package_files[&package.name];
+        format!("packages/{}", self.file(name))
+    }
+
+    fn link(&self, from: &str, to: &str) -> String {
+        format!("{}{to}", "../".repeat(from.matches('/').count()))
+    }
+
+    fn relative_to_root(&self, path: &str) -> String {
+        path.strip_prefix(self.scope.root.as_str())
+            .unwrap_or(path)
+            .trim_start_matches('/')
+            .to_string()
+    }
+
+    /// Removes the terminal colors some internal descriptions are
+    /// defined with
+    fn plain(text: &str) -> String {
+        Regex::new("\x1b\\[[0-9;]*m")
+            .expect("valid pattern")
+            .replace_all(text, "")
+            .to_string()
+    }
+
+    /// Computes the path of the target relative to a directory
+    fn relative(from: &Path, to: &Path) -> String {
+        let from: Vec<Component> = from.components().collect();
+        let to: Vec<Component> = to.components().collect();
+        let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+        let mut segments: Vec<String> = vec!["..".to_string(); from.len() - common];
+        segments.extend(
+            to[common..]
+                .iter()
+                .map(|x| x.as_os_str().to_string_lossy().to_string()),
+        );
+        segments.join("/")
+    }
+}
@@ -0,0 +1,262 @@
+use std::{env, fs, path::Path};
+
+use indexmap::IndexMap;
+
+pub struct DotEnv {
+    characters: Vec<char>,
+    index: usize,
+    line: usize,
+}
+
+impl DotEnv {
+    /// Reads a dotenv file into the provided variables, overwriting any
+    /// that were previously defined
+    pub fn read(path: &Path, variables: &mut IndexMap<String, String>) -> Result<(), String> {
+        let contents = fs::read_to_string(path)
+            .map_err(|error| format!("I could not read {}: {}", path.display(), error))?;
+        DotEnv::parse(&contents, variables)
+            .map_err(|error| format!("I could not parse {}: {}", path.display(), error))
+    }
+
+    pub fn parse(contents: &str, variables: &mut IndexMap<String, String>) -> Result<(), String> {
+        let mut parser = DotEnv {
+            characters: contents.chars().collect(),
+            index: 0,
+            line: 1,
+        };
+        while let Some((key, value)) = parser.entry(variables)? {
+            variables.insert(key, value);
+        }
+        Ok(())
+    }
+
+    /// Removes the variables that are already set in the process
+    /// environment, which always takes precedence
+    pub fn without_process_environment(
+        variables: IndexMap<String, String>,
+    ) -> IndexMap<String, String> {
+        variables
+            .into_iter()
+            .filter(|(key, _)| env::var_os(key).is_none())
+            .collect()
+    }
+
+    fn entry(
+        &mut self,
+        variables: &IndexMap<String, String>,
+    ) -> Result<Option<(String, String)>, String> {
+        loop {
+            self.skip(|c| c.is_whitespace());
+            match self.peek() {
+                None => return Ok(None),
+                Some('#') => self.skip(|c| c != '\n'),
+                Some(_) => break,
+            }
+        }
+        let mut key = self.take(|c| c != '=' && !c.is_whitespace());
+        if key == "export" && self.peek().is_some_and(|c| c == ' ' || c == '\t') {
+            self.skip(|c| c == ' ' || c == '\t');
+            key = self.take(|c| c != '=' && !c.is_whitespace());
+        }
+        let valid = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        if !valid {
+            return Err(format!(
+                "line {}: invalid variable name \"{key}\"",
+                self.line
+            ));
+        }
+        self.skip(|c| c == ' ' || c == '\t');
+        if self.peek() != Some('=') {
+            return Err(format!("line {}: expected = after {key}", self.line));
+        }
+        self.index += 1;
+        self.skip(|c| c == ' ' || c == '\t');
+        let value = match self.peek() {
+            Some('\'') => self.quoted('\'')?,
+            Some('"') => {
+                let raw = self.quoted('"')?;
+                DotEnv::interpret(&raw, true, variables)
+            }
+            _ => {
+                let raw = self.take(|c| c != '\n');
+                let raw = match raw.find(" #").or(raw.find("\t#")) {
+                    Some(comment) => &raw[..comment],
+                    None => &raw,
+                };
+                DotEnv::interpret(raw.trim_end(), false, variables)
+            }
+        };
+        self.skip(|c| c != '\n');
+        Ok(Some((key, value)))
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String, String> {
+        let line = self.line;
+        self.index += 1;
+        let mut raw = String::new();
+        while let Some(character) = self.peek() {
+            self.index += 1;
+            if character == quote {
+                return Ok(raw);
+            }
+            if character == '\n' {
+                self.line += 1;
+            }
+            raw.push(character);
+            if character == '\\'
+                && quote == '"'
+                && let Some(escaped) = self.peek().filter(|c| *c != '\n')
+            {
+                raw.push(escaped);
+                self.index += 1;
+            }
+        }
+        Err(format!("line {line}: missing closing {quote}"))
+    }
+
+    fn interpret(raw: &str, escapes: bool, variables: &IndexMap<String, String>) -> String {
+        let characters: Vec<char> = raw.chars().collect();
+        let mut output = String::with_capacity(raw.len());
+        let mut index = 0;
+        while index < characters.len() {
+            let character = characters[index];
+            index += 1;
+            if character == '\\' && escapes {
+                match characters.get(index) {
+                    Some('n') => output.push('\n'),
+                    Some('r') => output.push('\r'),
+                    Some('t') => output.push('\t'),
+                    Some(escaped) => output.push(*escaped),
+                    None => output.push('\\'),
+                }
+                index += 1;
+                continue;
+            }
+            if character != '$' {
+                output.push(character);
+                continue;
+            }
+            if characters.get(index) == Some(&'{') {
+                let Some(length) = characters[index..].iter().position(|c| *c == '}') else {
+                    output.push(character);
+                    continue;
+                };
+                let expression: String = characters[index + 1..index + length].iter().collect();
+                index += length + 1;
+                let (name, fallback) = match expression.split_once(":-") {
+                    Some((name, fallback)) => (name, Some(fallback)),
+                    None => (expression.as_str(), None),
+                };
+                match DotEnv::lookup(name, variables) {
+                    Some(value) if !value.is_empty() => output.push_str(&value),
+                    _ => output.push_str(fallback.unwrap_or_default()),
+                }
+                continue;
+            }
+            let name: String = characters[index..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                .collect();
+            if name.is_empty() {
+                output.push(character);
+                continue;
+            }
+            index += name.chars().count();
+            output.push_str(&DotEnv::lookup(&name, variables).unwrap_or_default());
+        }
+        output
+    }
+
+    fn lookup(name: &str, variables: &IndexMap<String, String>) -> Option<String> {
+        env::var(name).ok().or_else(|| variables.get(name).cloned())
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.characters.get(self.index).copied()
+    }
+
+    fn skip(&mut self, predicate: impl Fn(char) -> bool) {
+        self.take(predicate);
+    }
+
+    fn take(&mut self, predicate: impl Fn(char) -> bool) -> String {
+        let mut taken = String::new();
+        while let Some(character) = self.peek().filter(|c| predicate(*c)) {
+            if character == '\n' {
+                self.line += 1;
+            }
+            taken.push(character);
+            self.index += 1;
+        }
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::DotEnv;
+
+    fn parse(contents: &str) -> Result<IndexMap<String, String>, String> {
+        let mut variables = IndexMap::new();
+        DotEnv::parse(contents, &mut variables)?;
+        Ok(variables)
+    }
+
+    #[test]
+    fn reads_plain_values_and_comments() {
+        let variables =
+            parse("# comment\nREPOKIT_A=one two # trailing\n\nREPOKIT_B=a#b\n").unwrap();
+        assert_eq!(variables["REPOKIT_A"], "one two");
+        assert_eq!(variables["REPOKIT_B"], "a#b");
+    }
+
+    #[test]
+    fn reads_quoted_values() {
+        let variables = parse(
+            "REPOKIT_SINGLE='keep $REPOKIT_X \\n'\nREPOKIT_DOUBLE=\"tab\\there \\\"q\\\"\"\nREPOKIT_MULTI=\"a\nb\"\n",
+        )
+        .unwrap();
+        assert_eq!(variables["REPOKIT_SINGLE"], "keep $REPOKIT_X \\n");
+        assert_eq!(variables["REPOKIT_DOUBLE"], "tab\there \"q\"");
+        assert_eq!(variables["REPOKIT_MULTI"], "a\nb");
+    }
+
+    #[test]
+    fn ignores_the_export_prefix() {
+        let variables = parse("export REPOKIT_REGION=eu\nexport=kept\n").unwrap();
+        assert_eq!(variables["REPOKIT_REGION"], "eu");
+        assert_eq!(variables["export"], "kept");
+    }
+
+    #[test]
+    fn expands_variables() {
+        let variables = parse(
+            "REPOKIT_HOST=db\nREPOKIT_URL=${REPOKIT_HOST}:5432/$REPOKIT_HOST\nREPOKIT_PORT=${REPOKIT_UNSET:-8080}\nREPOKIT_EMPTY=$REPOKIT_UNSET\n",
+        )
+        .unwrap();
+        assert_eq!(variables["REPOKIT_URL"], "db:5432/db");
+        assert_eq!(variables["REPOKIT_PORT"], "8080");
+        assert_eq!(variables["REPOKIT_EMPTY"], "");
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        assert_eq!(
+            parse("REPOKIT_OK=1\n1BAD=2\n").unwrap_err(),
+            "line 2: invalid variable name \"1BAD\""
+        );
+        assert_eq!(
+            parse("REPOKIT_OPEN=\"never closed\n").unwrap_err(),
+            "line 1: missing closing \""
+        );
+        assert!(parse("REPOKIT_NO_EQUALS\n").is_err());
+    }
+}
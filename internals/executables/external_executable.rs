@@ -1,4 +0,0 @@
-trait ExternalExecutable {
-    fn run(command: String, args: Vec<String>);
-    fn help(&self);
-}
@@ -3,7 +3,14 @@ use std::collections::HashMap;
 use crate::executables::internal_executable_definition::InternalExecutableDefinition;
 
 pub trait InternalExecutable {
-    fn run(&self, args: Vec<String>, internals: &HashMap<String, Box<dyn InternalExecutable>>);
+    fn run(
+        &self,
+        args: Vec<String>,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> i32;
     fn help(&self);
     fn get_definition(&self) -> &InternalExecutableDefinition;
+    fn complete(&self, _args: &[String]) -> Vec<String> {
+        Vec::new()
+    }
 }
@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use crate::repokit::interfaces::RepoKitConfig;
+use crate::repokit::{interfaces::RepoKitConfig, options::RepoKitOptions};
 
 #[derive(Clone)]
 pub struct RepoKitScope {
     pub root: String,
     pub configuration: RepoKitConfig,
+    pub options: RepoKitOptions,
 }
 
 #[derive(Clone)]
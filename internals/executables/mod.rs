@@ -1,3 +1,2 @@
-pub mod external_executable;
 pub mod intenal_executable;
 pub mod internal_executable_definition;
@@ -0,0 +1,214 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use colored::ColoredString;
+use indexmap::IndexMap;
+use regex::Regex;
+
+use crate::{
+    executor::{
+        executor::Executor, process_group::ProcessGroup, retry_policy::RetryPolicy,
+        script_file::ScriptFile, shell::Shell,
+    },
+    logger::logger::Logger,
+    repokit::{exit_codes::ExitCodes, interfaces::CommandLine},
+};
+
+/// Everything repokit resolves before spawning a command
+pub struct ExecutionPlan {
+    pub command: CommandLine,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+    pub env: IndexMap<String, String>,
+    pub shell: Shell,
+    pub script: Option<String>,
+    pub retry: RetryPolicy,
+    pub manifest: PathBuf,
+    pub key: String,
+}
+
+impl ExecutionPlan {
+    /// The command exactly as it will be handed to the shell or
+    /// spawned directly
+    pub fn resolved(&self) -> String {
+        if self.script.is_some() {
+            let args = Executor::join("", &self.args);
+            return format!("{} <script>{args}", self.command);
+        }
+        match &self.command {
+            CommandLine::Shell(script) => Executor::join(script, &self.args),
+            CommandLine::Argv(argv) => {
+                Executor::join("", &[argv.clone(), self.args.clone()].concat())
+                    .trim()
+                    .to_string()
+            }
+        }
+    }
+
+    pub fn shell(&self) -> String {
+        if self.script.is_some() {
+            return "none, the script is run by its interpreter".to_string();
+        }
+        match self.command {
+            CommandLine::Shell(_) => self.shell.describe(),
+            CommandLine::Argv(_) => "none, the command is spawned directly".to_string(),
+        }
+    }
+
+    /// The manifest the command is defined in, along with the line of its
+    /// definition when it can be found
+    pub fn source(&self) -> String {
+        let path = self.manifest.to_string_lossy().to_string();
+        match ExecutionPlan::definition_line(&self.manifest, &self.key) {
+            Some(line) => format!("{path}:{line}"),
+            None => path,
+        }
+    }
+
+    pub fn log(&self, name: &str) {
+        Logger::info(
+            format!(
+                "Explaining {}. Nothing will be run\n",
+                Logger::blue_bright(name)
+            )
+            .as_str(),
+        );
+        let mut environment: Vec<String> = self
+            .env
+            .iter()
+            .map(|(key, value)| format!("{key}={}", Executor::quote(value).replace('\n', "\\n")))
+            .collect();
+        if environment.is_empty() {
+            environment.push("none".to_string());
+        }
+        let details = [
+            ("Command:", vec![Logger::green_bright(&self.resolved())]),
+            ("Shell:", vec![Logger::cyan(&self.shell())]),
+            (
+                "Directory:",
+                vec![Logger::cyan(&self.cwd.to_string_lossy())],
+            ),
+            (
+                "Environment:",
+                environment.iter().map(|x| Logger::cyan(x)).collect(),
+            ),
+            ("Defined in:", vec![Logger::blue_bright(&self.source())]),
+        ];
+        let script: Vec<ColoredString> = match &self.script {
+            Some(script) => script.lines().map(Logger::green).collect(),
+            None => Vec::new(),
+        };
+        let timeout = self.retry.timeout.map(Logger::format_duration);
+        let retries = self.retry.describe_retries();
+        let policy: Vec<(&str, Vec<ColoredString>)> =
+            [("Timeout:", timeout), ("Retries:", retries)]
+                .into_iter()
+                .map(|(label, value)| (label, value.iter().map(|x| Logger::cyan(x)).collect()))
+                .collect();
+        let (location, details) = details.split_last().expect("details");
+        let details = [
+            &details[..1],
+            &[("Script:", script)],
+            &details[1..],
+            &policy,
+            std::slice::from_ref(location),
+        ]
+        .concat();
+        for (label, values) in details {
+            for (index, value) in values.iter().enumerate() {
+                let label = if index == 0 { label } else { "" };
+                println!(
+                    "{}{}{value}",
+                    Logger::indent(Some(3)),
+                    Logger::gray(&format!("{label:<14}"))
+                );
+            }
+        }
+        println!();
+    }
+
+    /// Runs the command, writing inline scripts to a temporary file that
+    /// is removed once the command exits
+    pub fn run(&self) -> i32 {
+        let (Some(body), CommandLine::Argv(interpreter)) = (&self.script, &self.command) else {
+            return self.attempt(&self.command);
+        };
+        let extension = Shell::extension(interpreter.first().map_or("", |x| x.as_str()));
+        let file = match ScriptFile::write(body, extension) {
+            Ok(file) => file,
+            Err(error) => {
+                Logger::error(format!("I could not write the script to run: {error}").as_str());
+                return ExitCodes::FAILURE;
+            }
+        };
+        let path = file.path().to_string_lossy().to_string();
+        self.attempt(&CommandLine::Argv(
+            [interpreter.clone(), vec![path]].concat(),
+        ))
+    }
+
+    /// Runs the command until it succeeds or exhausts its retries,
+    /// returning the exit code of the final attempt. Commands interrupted
+    /// by the user are never retried
+    fn attempt(&self, command: &CommandLine) -> i32 {
+        let attempts = self.retry.attempts();
+        let mut attempt = 1;
+        loop {
+            let code = Executor::with_stdio(
+                command,
+                &self.args,
+                &self.shell,
+                self.retry.timeout,
+                |cmd| cmd.current_dir(&self.cwd).envs(&self.env),
+            );
+            let interrupted = ProcessGroup::interrupted().is_some();
+            if code == ExitCodes::SUCCESS || code == ExitCodes::INTERRUPTED || interrupted {
+                return code;
+            }
+            if attempt == attempts {
+                if attempts > 1 {
+                    Logger::error(
+                        format!("All {attempts} attempts failed. The last exited with {code}")
+                            .as_str(),
+                    );
+                }
+                return code;
+            }
+            let delay = self.retry.delay(attempt);
+            Logger::error(
+                format!(
+                    "Attempt {attempt} of {attempts} exited with {code}. Retrying in {}",
+                    Logger::format_duration(delay)
+                )
+                .as_str(),
+            );
+            ProcessGroup::sleep(delay);
+            if let Some(signal) = ProcessGroup::interrupted() {
+                return ExitCodes::SIGNAL_OFFSET + signal;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Finds the line defining a command by scanning for its key after
+    /// the manifest's first commands key
+    fn definition_line(manifest: &Path, name: &str) -> Option<usize> {
+        let contents = fs::read_to_string(manifest).ok()?;
+        let commands = Regex::new(r#"^\s*\[?["']?commands["']?\s*[:=.\]]"#).expect("valid");
+        let key = Regex::new(&format!(
+            r#"^\s*(\[commands\.)?["']?{}["']?\s*[:=\]]"#,
+            regex::escape(name)
+        ))
+        .expect("valid");
+        let lines: Vec<&str> = contents.lines().collect();
+        let start = lines.iter().position(|x| commands.is_match(x)).unwrap_or(0);
+        lines
+            .iter()
+            .enumerate()
+            .skip(start)
+            .find(|(_, line)| key.is_match(line))
+            .map(|(index, _)| index + 1)
+    }
+}
@@ -1,6 +1,17 @@
 use std::ffi::OsStr;
-use std::process::Command;
+use std::io::ErrorKind;
+use std::process::{Command, ExitStatus};
 use std::str;
+use std::time::Duration;
+
+use crate::{
+    executor::{
+        process_group::{Completion, ProcessGroup},
+        shell::Shell,
+    },
+    logger::logger::Logger,
+    repokit::{exit_codes::ExitCodes, interfaces::CommandLine},
+};
 
 pub struct Executor {}
 
@@ -18,38 +29,118 @@ impl Executor {
         Executor::unwrap(&output.stderr)
     }
 
-    pub fn with_stdio<T: AsRef<OsStr>>(
-        command: T,
+    pub fn with_stdio(
+        command: &CommandLine,
+        args: &[String],
+        shell: &Shell,
+        timeout: Option<Duration>,
         composer: impl Fn(&mut Command) -> &mut Command,
-    ) {
-        let mut child = composer(&mut Executor::spawn(command))
-            .spawn()
-            .expect("Failed to execute");
-        child.wait().expect("failed to wait on child process");
+    ) -> i32 {
+        let Some(mut process) = Executor::compose(command, args, shell) else {
+            Logger::error("This command does not define anything to run");
+            return ExitCodes::FAILURE;
+        };
+        let mut group = match ProcessGroup::spawn(composer(&mut process)) {
+            Ok(group) => group,
+            Err(error) if error.kind() == ErrorKind::NotFound => {
+                if let CommandLine::Shell(_) = command {
+                    Logger::error(
+                        format!(
+                            "I could not find the shell {} to run {}",
+                            shell.program, command
+                        )
+                        .as_str(),
+                    );
+                } else {
+                    Logger::error(format!("I could not run {}: {}", command, error).as_str());
+                }
+                return ExitCodes::COMMAND_NOT_FOUND;
+            }
+            Err(error) => {
+                Logger::error(format!("I could not run {}: {}", command, error).as_str());
+                return ExitCodes::FAILURE;
+            }
+        };
+        match group.wait(timeout) {
+            Ok(Completion::Exited(code)) => code,
+            Ok(Completion::TimedOut) => {
+                Logger::error(
+                    format!(
+                        "{} timed out after {}. Stopping it",
+                        command,
+                        Logger::format_duration(timeout.unwrap_or_default())
+                    )
+                    .as_str(),
+                );
+                let _ = group.terminate();
+                ExitCodes::TIMEOUT
+            }
+            Err(error) => {
+                Logger::error(format!("I could not wait for {}: {}", command, error).as_str());
+                ExitCodes::FAILURE
+            }
+        }
     }
 
-    pub fn spawn<T: AsRef<OsStr>>(program: T) -> Command {
-        let mut command = Executor::platform_command();
-        command.arg(program);
-        command
+    pub fn compose(command: &CommandLine, args: &[String], shell: &Shell) -> Option<Command> {
+        match command {
+            CommandLine::Shell(script) => Some(shell.command(&Executor::join(script, args))),
+            CommandLine::Argv(argv) => {
+                let (program, rest) = argv.split_first()?;
+                let mut process = Command::new(program);
+                process.args(rest).args(args);
+                Some(process)
+            }
+        }
     }
 
-    fn platform_command() -> Command {
+    pub fn join(script: &str, args: &[String]) -> String {
+        let mut line = script.to_string();
+        for arg in args {
+            line.push(' ');
+            line.push_str(&Executor::quote(arg));
+        }
+        line
+    }
+
+    pub fn quote(arg: &str) -> String {
+        let safe = !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_./:=,+@%".contains(c));
+        if safe {
+            return arg.to_string();
+        }
         if cfg!(target_os = "windows") {
-            return Executor::windows_command();
+            return format!("\"{}\"", arg.replace('"', "\\\""));
         }
-        Executor::unix_command()
+        format!("'{}'", arg.replace('\'', "'\\''"))
     }
 
-    fn windows_command() -> Command {
-        let mut child_process = Command::new("cmd");
-        child_process.arg("/C");
-        child_process
+    pub fn exit_code(status: ExitStatus) -> i32 {
+        if let Some(code) = status.code() {
+            return code;
+        }
+        #[cfg(unix)]
+        {
+            use std::os::unix::process::ExitStatusExt;
+            if let Some(signal) = status.signal() {
+                return ExitCodes::SIGNAL_OFFSET + signal;
+            }
+        }
+        ExitCodes::FAILURE
     }
 
-    fn unix_command() -> Command {
-        let mut child_process = Command::new("sh");
-        child_process.arg("-c");
+    pub fn spawn<T: AsRef<OsStr>>(program: T) -> Command {
+        let mut command = Executor::platform_command();
+        command.arg(program);
+        command
+    }
+
+    fn platform_command() -> Command {
+        let shell = Shell::platform();
+        let mut child_process = Command::new(&shell.program);
+        child_process.args(&shell.flags);
         child_process
     }
 
@@ -0,0 +1,157 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    executor::executor::Executor, repokit::interfaces::CommandLine,
+    validations::argument_validations::ParsedArguments,
+};
+
+pub struct InterpolationContext<'a> {
+    pub root: &'a str,
+    pub package_dir: &'a Path,
+    pub working_dir: &'a Path,
+    pub command_name: &'a str,
+}
+
+pub struct Interpolation<'a> {
+    arguments: &'a ParsedArguments,
+    context: &'a InterpolationContext<'a>,
+    quoted: bool,
+    /// Whether {{args}} was used, so that no arguments are appended
+    consumed: bool,
+    /// The positions in argv of arguments used by a placeholder
+    used: Vec<usize>,
+}
+
+impl<'a> Interpolation<'a> {
+    /// Resolves the placeholders in a command. Returns the command along
+    /// with the arguments that should still be appended to it, which are
+    /// those not used by a placeholder unless {{args}} is used
+    pub fn resolve(
+        command: &CommandLine,
+        arguments: &'a ParsedArguments,
+        context: &'a InterpolationContext<'a>,
+    ) -> Result<(CommandLine, Vec<String>), String> {
+        let mut interpolation = Interpolation {
+            arguments,
+            context,
+            quoted: matches!(command, CommandLine::Shell(_)),
+            consumed: false,
+            used: Vec::new(),
+        };
+        let resolved = match command {
+            CommandLine::Shell(script) => CommandLine::Shell(interpolation.interpolate(script)?),
+            CommandLine::Argv(argv) => {
+                let mut resolved = Vec::with_capacity(argv.len());
+                for token in argv {
+                    if token.trim() == "{{args}}" {
+                        interpolation.consumed = true;
+                        resolved.extend(arguments.argv.iter().cloned());
+                        continue;
+                    }
+                    resolved.push(interpolation.interpolate(token)?);
+                }
+                CommandLine::Argv(resolved)
+            }
+        };
+        let remaining = match interpolation.consumed {
+            true => Vec::new(),
+            false => arguments
+                .argv
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !interpolation.used.contains(index))
+                .map(|(_, arg)| arg.clone())
+                .collect(),
+        };
+        Ok((resolved, remaining))
+    }
+
+    /// Resolves a path relative to the package. Paths beginning with
+    /// {{root}} are anchored to the repository root instead
+    pub fn path(path: &str, root: &str, package_dir: &Path) -> PathBuf {
+        match path.trim().strip_prefix("{{root}}") {
+            Some(rest) => Path::new(root).join(rest.trim_start_matches(['/', '\\'])),
+            None => package_dir.join(path.trim()),
+        }
+    }
+
+    fn interpolate(&mut self, source: &str) -> Result<String, String> {
+        let mut output = String::with_capacity(source.len());
+        let mut rest = source;
+        while let Some(start) = rest.find("{{") {
+            let Some(length) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            output.push_str(&rest[..start]);
+            let placeholder = rest[start + 2..start + 2 + length].trim();
+            let (name, optional) = match placeholder.strip_suffix('?') {
+                Some(name) => (name.trim(), true),
+                None => (placeholder, false),
+            };
+            match self.lookup(name) {
+                Some(value) => output.push_str(&value),
+                None if optional => {}
+                None => {
+                    return Err(format!(
+                        "The placeholder {{{{{name}}}}} could not be resolved"
+                    ));
+                }
+            }
+            rest = &rest[start + 2 + length + 2..];
+        }
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    fn lookup(&mut self, name: &str) -> Option<String> {
+        if name == "args" {
+            self.consumed = true;
+            return Some(self.join(&self.arguments.argv));
+        }
+        if let Ok(index) = name.parse::<usize>() {
+            let index = index.checked_sub(1)?;
+            let value = self.arguments.positionals.get(index)?;
+            if let Some(position) = self.arguments.positional_indices.get(index) {
+                self.used.push(*position);
+            }
+            return Some(self.join(std::slice::from_ref(value)));
+        }
+        if let Some(values) = self.arguments.named.get(name) {
+            let claimed = self.arguments.claims.iter().enumerate();
+            self.used.extend(
+                claimed
+                    .filter(|(_, claim)| claim.as_deref() == Some(name))
+                    .map(|(position, _)| position),
+            );
+            return Some(self.join(values));
+        }
+        self.built_in(name)
+    }
+
+    fn built_in(&self, name: &str) -> Option<String> {
+        let value = match name {
+            "root" => self.context.root.to_string(),
+            "package_dir" => self.context.package_dir.to_str()?.to_string(),
+            "command_name" => self.context.command_name.to_string(),
+            "git_branch" => Executor::exec("git branch --show-current 2>/dev/null", |cmd| {
+                cmd.current_dir(self.context.package_dir)
+            }),
+            _ => return None,
+        };
+        if value.is_empty() {
+            return None;
+        }
+        Some(self.join(&[value]))
+    }
+
+    fn join(&self, values: &[String]) -> String {
+        if !self.quoted {
+            return values.join(" ");
+        }
+        values
+            .iter()
+            .map(|value| Executor::quote(value))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
@@ -1 +1,7 @@
+pub mod execution_plan;
 pub mod executor;
+pub mod interpolation;
+pub mod process_group;
+pub mod retry_policy;
+pub mod script_file;
+pub mod shell;
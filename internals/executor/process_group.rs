@@ -0,0 +1,298 @@
+use std::{
+    io,
+    process::{Child, Command},
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{executor::executor::Executor, repokit::exit_codes::ExitCodes};
+
+/// How often a running group is checked for its exit
+static POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How long a group has to exit after being signalled before it is sent
+/// SIGKILL
+static KILL_GRACE_PERIOD: Duration = Duration::from_secs(5);
+
+/// Signals sent to repokit that are forwarded to the running group
+#[cfg(unix)]
+static FORWARDED_SIGNALS: [libc::c_int; 3] = [libc::SIGINT, libc::SIGTERM, libc::SIGHUP];
+
+/// The last forwarded signal repokit received, along with the signal
+/// waiting to be forwarded to the running group
+static SIGNALS: OnceLock<(Arc<AtomicUsize>, Arc<AtomicUsize>)> = OnceLock::new();
+
+pub enum Completion {
+    Exited(i32),
+    TimedOut,
+}
+
+/// A child process running in a process group of its own, so that it
+/// and everything it spawns can be signalled together. When repokit is
+/// in the foreground of a terminal, the terminal is handed to the group
+/// for as long as it runs
+pub struct ProcessGroup {
+    child: Child,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    foreground: bool,
+    forwarded: bool,
+}
+
+impl ProcessGroup {
+    /// Waits for the group's leader to exit, returning early once the
+    /// timeout expires
+    pub fn wait(&mut self, timeout: Option<Duration>) -> io::Result<Completion> {
+        let deadline = timeout.map(|x| Instant::now() + x);
+        loop {
+            self.forward();
+            if let Some(code) = self.poll()? {
+                return Ok(Completion::Exited(self.settle(code)?));
+            }
+            if deadline.is_some_and(|x| Instant::now() >= x) {
+                return Ok(Completion::TimedOut);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// The last signal repokit received and forwarded, if any
+    pub fn interrupted() -> Option<i32> {
+        let (received, _) = SIGNALS.get()?;
+        match received.load(Ordering::SeqCst) {
+            0 => None,
+            signal => Some(signal as i32),
+        }
+    }
+
+    /// Sleeps for the duration, returning early when repokit receives a
+    /// signal meant for its commands
+    pub fn sleep(duration: Duration) {
+        let deadline = Instant::now() + duration;
+        while ProcessGroup::interrupted().is_none() && Instant::now() < deadline {
+            thread::sleep(POLL_INTERVAL.min(deadline - Instant::now()));
+        }
+    }
+
+    /// Sends signals repokit receives to the running group, rather than
+    /// letting them terminate repokit while the group lives on
+    fn forward(&mut self) {
+        let Some((_, pending)) = SIGNALS.get() else {
+            return;
+        };
+        let signal = pending.swap(0, Ordering::SeqCst);
+        if signal != 0 {
+            self.forwarded = true;
+            self.signal(signal as i32);
+        }
+    }
+}
+
+#[cfg(unix)]
+impl ProcessGroup {
+    pub fn spawn(command: &mut Command) -> io::Result<ProcessGroup> {
+        use std::os::unix::process::CommandExt;
+        ProcessGroup::listen();
+        let foreground = ProcessGroup::owns_terminal();
+        // Runs in the child between fork and exec, so it may only make
+        // async-signal-safe calls
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setpgid(0, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if foreground {
+                    libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+                    libc::tcsetpgrp(libc::STDIN_FILENO, libc::getpid());
+                    libc::signal(libc::SIGTTOU, libc::SIG_DFL);
+                }
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+        // Also moves the child into its group from the parent, so that
+        // the group exists before any signal is forwarded to it. Fails
+        // harmlessly with EACCES once the child has exec'd
+        unsafe {
+            let id = child.id() as libc::pid_t;
+            libc::setpgid(id, id);
+        }
+        Ok(ProcessGroup {
+            child,
+            foreground,
+            forwarded: false,
+        })
+    }
+
+    /// Sends SIGTERM to the group, then SIGKILL to whatever remains of
+    /// it once the grace period expires
+    pub fn terminate(&mut self) -> io::Result<i32> {
+        self.signal(libc::SIGTERM);
+        self.drain(None)
+    }
+
+    /// Installs handlers for the signals forwarded to running groups.
+    /// They remain installed once the first group is spawned, so that a
+    /// signal arriving between retries is not lost
+    fn listen() {
+        SIGNALS.get_or_init(|| {
+            let received = Arc::new(AtomicUsize::new(0));
+            let pending = Arc::new(AtomicUsize::new(0));
+            for signal in FORWARDED_SIGNALS {
+                let _ =
+                    signal_hook::flag::register_usize(signal, received.clone(), signal as usize);
+                let _ = signal_hook::flag::register_usize(signal, pending.clone(), signal as usize);
+            }
+            (received, pending)
+        });
+    }
+
+    /// Once the leader exits after being signalled, waits for the rest of
+    /// the group to follow it so that nothing it started is orphaned
+    fn settle(&mut self, code: i32) -> io::Result<i32> {
+        let signalled = self.forwarded || code > ExitCodes::SIGNAL_OFFSET;
+        if signalled && self.is_alive() {
+            return self.drain(Some(code));
+        }
+        Ok(code)
+    }
+
+    /// Waits for the leader and every other member of the group to exit,
+    /// sending SIGKILL to whatever remains once the grace period expires
+    fn drain(&mut self, mut code: Option<i32>) -> io::Result<i32> {
+        let deadline = Instant::now() + KILL_GRACE_PERIOD;
+        while Instant::now() < deadline {
+            self.forward();
+            if code.is_none() {
+                code = self.poll()?;
+            }
+            if let Some(code) = code
+                && !self.is_alive()
+            {
+                return Ok(code);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        self.signal(libc::SIGKILL);
+        if let Some(code) = code {
+            return Ok(code);
+        }
+        loop {
+            if let Some(code) = self.poll()? {
+                return Ok(code);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn signal(&self, signal: libc::c_int) {
+        unsafe {
+            libc::kill(-self.id(), signal);
+        }
+    }
+
+    /// Whether any member of the group is still running
+    fn is_alive(&self) -> bool {
+        unsafe { libc::kill(-self.id(), 0) == 0 }
+    }
+
+    /// Reaps the leader when it has exited. When the group is stopped
+    /// from the terminal, repokit stops alongside it so that the shell
+    /// regains control, and resumes the group once it is continued
+    fn poll(&mut self) -> io::Result<Option<i32>> {
+        use std::os::unix::process::ExitStatusExt;
+        let mut status = 0;
+        let result =
+            unsafe { libc::waitpid(self.id(), &mut status, libc::WNOHANG | libc::WUNTRACED) };
+        if result == -1 {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::Interrupted {
+                return Ok(None);
+            }
+            return Err(error);
+        }
+        if result == 0 {
+            return Ok(None);
+        }
+        if libc::WIFSTOPPED(status) {
+            self.suspend();
+            return Ok(None);
+        }
+        let status = std::process::ExitStatus::from_raw(status);
+        Ok(Some(Executor::exit_code(status)))
+    }
+
+    fn suspend(&self) {
+        if self.foreground {
+            ProcessGroup::give_terminal(unsafe { libc::getpgrp() });
+        }
+        unsafe {
+            libc::kill(libc::getpid(), libc::SIGSTOP);
+        }
+        if self.foreground {
+            ProcessGroup::give_terminal(self.id());
+        }
+        self.signal(libc::SIGCONT);
+    }
+
+    fn id(&self) -> libc::pid_t {
+        self.child.id() as libc::pid_t
+    }
+
+    /// Whether repokit's process group is in the foreground of the
+    /// terminal attached to stdin
+    fn owns_terminal() -> bool {
+        unsafe {
+            libc::isatty(libc::STDIN_FILENO) == 1
+                && libc::tcgetpgrp(libc::STDIN_FILENO) == libc::getpgrp()
+        }
+    }
+
+    /// Moves a process group to the foreground of the terminal. SIGTTOU
+    /// is ignored, since repokit is in the background while it does so
+    fn give_terminal(group: libc::pid_t) {
+        unsafe {
+            let previous = libc::signal(libc::SIGTTOU, libc::SIG_IGN);
+            libc::tcsetpgrp(libc::STDIN_FILENO, group);
+            libc::signal(libc::SIGTTOU, previous);
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for ProcessGroup {
+    fn drop(&mut self) {
+        if self.foreground {
+            ProcessGroup::give_terminal(unsafe { libc::getpgrp() });
+        }
+    }
+}
+
+#[cfg(not(unix))]
+impl ProcessGroup {
+    pub fn spawn(command: &mut Command) -> io::Result<ProcessGroup> {
+        Ok(ProcessGroup {
+            child: command.spawn()?,
+            foreground: false,
+            forwarded: false,
+        })
+    }
+
+    pub fn terminate(&mut self) -> io::Result<i32> {
+        self.child.kill()?;
+        Ok(Executor::exit_code(self.child.wait()?))
+    }
+
+    fn settle(&mut self, code: i32) -> io::Result<i32> {
+        Ok(code)
+    }
+
+    fn signal(&self, _: i32) {}
+
+    fn poll(&mut self) -> io::Result<Option<i32>> {
+        Ok(self.child.try_wait()?.map(Executor::exit_code))
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Duration;
+
+use crate::{logger::logger::Logger, repokit::interfaces::CommandDefinition};
+
+/// How long a command may run and how often it is retried when it fails
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
+    pub delay: Duration,
+    pub backoff: f64,
+}
+
+static DEFAULT_DELAY: Duration = Duration::from_secs(1);
+static DEFAULT_BACKOFF: f64 = 2.0;
+
+impl RetryPolicy {
+    pub fn from(definition: &CommandDefinition) -> Result<RetryPolicy, String> {
+        let backoff = definition.backoff.unwrap_or(DEFAULT_BACKOFF);
+        if !backoff.is_finite() || backoff < 1.0 {
+            return Err(format!(
+                "The backoff {backoff} is invalid. Specify a multiplier of at least 1"
+            ));
+        }
+        Ok(RetryPolicy {
+            timeout: definition.timeout.as_ref().map(|x| x.parse()).transpose()?,
+            retries: definition.retries,
+            delay: match &definition.retry_delay {
+                Some(delay) => delay.parse()?,
+                None => DEFAULT_DELAY,
+            },
+            backoff,
+        })
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.retries + 1
+    }
+
+    /// The delay before the nth retry, multiplied by the backoff after
+    /// each one
+    pub fn delay(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let seconds = self.delay.as_secs_f64() * self.backoff.powi(exponent);
+        Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX)
+    }
+
+    pub fn describe_retries(&self) -> Option<String> {
+        if self.retries == 0 {
+            return None;
+        }
+        let plural_appendage = if self.retries == 1 { "y" } else { "ies" };
+        let mut description = format!(
+            "{} retr{plural_appendage}, {} apart",
+            self.retries,
+            Logger::format_duration(self.delay)
+        );
+        if self.retries > 1 && self.backoff > 1.0 {
+            description.push_str(&format!(" and growing {}x each time", self.backoff));
+        }
+        Some(description)
+    }
+}
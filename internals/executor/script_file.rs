@@ -0,0 +1,75 @@
+use std::{
+    collections::hash_map::RandomState,
+    env,
+    fs::{self, File, OpenOptions},
+    hash::{BuildHasher, Hasher},
+    io::{self, ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+/// How many names are tried before giving up on creating a script file
+static MAX_ATTEMPTS: u32 = 16;
+
+/// An inline script written to a temporary file for the length of a run
+pub struct ScriptFile {
+    path: PathBuf,
+}
+
+impl ScriptFile {
+    /// Writes the script to a newly created file with a random name, so
+    /// that an existing file or symlink at that path is never written to
+    pub fn write(body: &str, extension: &str) -> io::Result<ScriptFile> {
+        for _ in 0..MAX_ATTEMPTS {
+            let path = env::temp_dir().join(format!(
+                "repokit-{}-{:016x}{extension}",
+                process::id(),
+                ScriptFile::random()
+            ));
+            match ScriptFile::create(&path) {
+                Ok(mut file) => {
+                    let script = ScriptFile { path };
+                    file.write_all(body.as_bytes())?;
+                    return Ok(script);
+                }
+                Err(error) if error.kind() == ErrorKind::AlreadyExists => continue,
+                Err(error) => return Err(error),
+            }
+        }
+        Err(io::Error::new(
+            ErrorKind::AlreadyExists,
+            "could not find an unused name for a temporary script",
+        ))
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[cfg(unix)]
+    fn create(path: &Path) -> io::Result<File> {
+        use std::os::unix::fs::OpenOptionsExt;
+        OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o700)
+            .open(path)
+    }
+
+    #[cfg(not(unix))]
+    fn create(path: &Path) -> io::Result<File> {
+        OpenOptions::new().write(true).create_new(true).open(path)
+    }
+
+    fn random() -> u64 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u32(process::id());
+        hasher.finish()
+    }
+}
+
+impl Drop for ScriptFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
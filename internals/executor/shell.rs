@@ -0,0 +1,137 @@
+use std::{path::Path, process::Command};
+
+use crate::repokit::interfaces::ShellSetting;
+
+/// The interpreter a command's script is handed to
+#[derive(Debug, Clone)]
+pub struct Shell {
+    pub program: String,
+    pub flags: Vec<String>,
+    pub strict: bool,
+}
+
+impl Shell {
+    pub fn platform() -> Shell {
+        if cfg!(target_os = "windows") {
+            return Shell::new("cmd", None, false);
+        }
+        Shell::new("sh", None, false)
+    }
+
+    pub fn new(program: &str, flags: Option<Vec<String>>, strict: bool) -> Shell {
+        Shell {
+            program: program.to_string(),
+            flags: flags.unwrap_or_else(|| Shell::default_flags(program)),
+            strict,
+        }
+    }
+
+    /// Layers shell settings from the most specific to the least. The
+    /// program and strict mode are each taken from the first setting
+    /// that defines them
+    pub fn resolve(settings: &[Option<&ShellSetting>]) -> Result<Shell, String> {
+        let mut program = None;
+        let mut strict = None;
+        for options in settings.iter().flatten().map(|x| x.options()) {
+            if program.is_none()
+                && let Some(name) = options.program
+            {
+                program = Some((name, options.flags));
+            }
+            strict = strict.or(options.strict);
+        }
+        let strict = strict.unwrap_or(false);
+        let shell = match program {
+            Some((program, flags)) => Shell::new(&program, flags, strict),
+            None => Shell {
+                strict,
+                ..Shell::platform()
+            },
+        };
+        if shell.strict && shell.strict_mode().is_none() {
+            return Err(format!(
+                "Strict mode is not supported when running commands with {}",
+                shell.program
+            ));
+        }
+        Ok(shell)
+    }
+
+    /// Composes a process running the script
+    pub fn command(&self, script: &str) -> Command {
+        let mut command = Command::new(&self.program);
+        command.args(&self.flags).arg(self.script(script));
+        command
+    }
+
+    /// Precedes the script with the strict mode preamble when enabled
+    pub fn script(&self, script: &str) -> String {
+        match self.strict_mode().filter(|_| self.strict) {
+            Some(preamble) => format!("{preamble}\n{script}"),
+            None => script.to_string(),
+        }
+    }
+
+    /// The program and flags that run a script file with this shell
+    pub fn interpreter(&self) -> Vec<String> {
+        let flags: &[&str] = match Shell::name(&self.program).as_str() {
+            "pwsh" | "powershell" => &["-NoProfile", "-File"],
+            "cmd" => &["/C"],
+            _ => &[],
+        };
+        [
+            vec![self.program.clone()],
+            flags.iter().map(|x| x.to_string()).collect(),
+        ]
+        .concat()
+    }
+
+    /// The file extension an interpreter requires scripts to have
+    pub fn extension(program: &str) -> &'static str {
+        match Shell::name(program).as_str() {
+            "pwsh" | "powershell" => ".ps1",
+            "cmd" => ".cmd",
+            _ => "",
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let invocation = [vec![self.program.clone()], self.flags.clone()]
+            .concat()
+            .join(" ");
+        match self.strict_mode().filter(|_| self.strict) {
+            Some(preamble) => format!("{invocation} (strict: {preamble})"),
+            None => invocation,
+        }
+    }
+
+    /// The statements that make the shell exit on the first failure.
+    /// POSIX sh is not guaranteed to support pipefail
+    fn strict_mode(&self) -> Option<&'static str> {
+        match Shell::name(&self.program).as_str() {
+            "bash" | "zsh" | "ksh" => Some("set -euo pipefail"),
+            "sh" | "dash" | "ash" => Some("set -eu"),
+            "pwsh" | "powershell" => Some("$ErrorActionPreference = 'Stop'"),
+            _ => None,
+        }
+    }
+
+    fn default_flags(program: &str) -> Vec<String> {
+        let flags: &[&str] = match Shell::name(program).as_str() {
+            "pwsh" | "powershell" => &["-NoProfile", "-Command"],
+            "cmd" => &["/C"],
+            _ => &["-c"],
+        };
+        flags.iter().map(|x| x.to_string()).collect()
+    }
+
+    /// The file name of the program without its extension, so both
+    /// /usr/local/bin/bash and pwsh.exe are recognized
+    fn name(program: &str) -> String {
+        Path::new(program)
+            .file_stem()
+            .and_then(|x| x.to_str())
+            .unwrap_or(program)
+            .to_lowercase()
+    }
+}
@@ -9,7 +9,14 @@ use jwalk::WalkDir;
 use tokio_thread_pool::ThreadPool;
 
 use crate::{
-    internal_commands::typescript_command::TypescriptCommand, repokit::interfaces::RepoKitCommand,
+    command_cache::command_cache::CommandCache,
+    internal_commands::typescript_command::TypescriptCommand,
+    logger::logger::Logger,
+    repokit::interfaces::RepoKitCommand,
+    static_parser::{
+        data_parser::DataParser,
+        static_parser::{ParseStrategy, StaticParser},
+    },
 };
 
 pub struct ExternalCommands {
@@ -24,6 +31,8 @@ impl ExternalCommands {
     }
 
     pub async fn find_all(&self) -> Vec<RepoKitCommand> {
+        let mut cache = CommandCache::new(&self.root);
+        let mut paths: Vec<String> = Vec::new();
         let mut futures = FuturesUnordered::new();
         let mut pool = ThreadPool::new(None, None, None);
         for entry in WalkDir::new(&self.root).into_iter().filter_map(|e| {
@@ -35,7 +44,9 @@ impl ExternalCommands {
                 Some(file) => {
                     let path = file.path();
                     if file.file_type().is_file()
-                        && path.extension().is_some_and(|ext| ext == "ts")
+                        && (path.extension().is_some_and(|ext| ext == "ts")
+                            || (DataParser::is_manifest(&path)
+                                && path.parent() != Some(Path::new(&self.root))))
                         && self.allowed(path.to_str().expect("exists"))
                     {
                         return Some(file);
@@ -46,6 +57,11 @@ impl ExternalCommands {
             }
         }) {
             let path = entry.path();
+            let manifest = self.relative(&path);
+            if DataParser::is_manifest(&path) || cache.is_fresh(&manifest) {
+                paths.push(manifest);
+                continue;
+            }
             futures.push(pool.spawn(move || {
                 if ExternalCommands::read(&path) {
                     return Some(path.clone());
@@ -53,20 +69,67 @@ impl ExternalCommands {
                 None
             }));
         }
-        let mut paths: Vec<String> = Vec::new();
         while let Ok(Some(buffer)) = futures.try_next().await {
             if let Some(path) = buffer {
-                paths.push(
-                    (path)
-                        .into_os_string()
-                        .into_string()
-                        .expect("stringify")
-                        .replace(&self.root, ""),
-                )
+                paths.push(self.relative(&path))
             }
         }
         pool.pool.shutdown_background();
-        TypescriptCommand::new(&self.root).parse_commands(paths)
+        let (mut commands, stale) = cache.partition(&paths);
+        let mut computed: Vec<String> = Vec::new();
+        for manifest in stale {
+            let path = format!("{}{}", self.root, manifest);
+            if let Some(strategy) = DataParser::strategy(Path::new(&path)) {
+                match DataParser::parse_command(Path::new(&path)) {
+                    Ok(command) => {
+                        cache.store(&manifest, strategy, vec![command.clone()]);
+                        commands.push(command);
+                    }
+                    Err(error) => DataParser::exit_on_failure(Path::new(&path), &error),
+                }
+                continue;
+            }
+            match StaticParser::parse_commands(Path::new(&path)) {
+                Ok(parsed) => {
+                    cache.store(&manifest, ParseStrategy::Static, parsed.clone());
+                    commands.extend(parsed);
+                }
+                Err(error) => {
+                    Logger::info(
+                        format!(
+                            "Evaluating {} with tsx because {}",
+                            Logger::blue_bright(&manifest),
+                            error.describe()
+                        )
+                        .as_str(),
+                    );
+                    computed.push(manifest);
+                }
+            }
+        }
+        if !computed.is_empty() {
+            let parsed = TypescriptCommand::new(&self.root).parse_commands(computed.clone());
+            for manifest in &computed {
+                let location = format!("{}{}", self.root, manifest);
+                cache.store(
+                    manifest,
+                    ParseStrategy::Typescript,
+                    parsed
+                        .iter()
+                        .filter(|command| command.location == location)
+                        .cloned()
+                        .collect(),
+                );
+            }
+            commands.extend(parsed);
+        }
+        cache.retain(&paths);
+        cache.save();
+        commands
+    }
+
+    fn relative(&self, path: &Path) -> String {
+        path.to_str().expect("stringify").replace(&self.root, "")
     }
 
     fn read(path: &Path) -> bool {
@@ -82,7 +145,7 @@ impl ExternalCommands {
     }
 
     fn allowed(&self, path: &str) -> bool {
-        let restricted_paths = ["node_modules", "target", "dist"];
+        let restricted_paths = ["node_modules", "target", "dist", ".repokit"];
         let restricted_extensions = ["templates/command_template.ts"];
         let relative_path = path.replace(format!("{}/", &self.root).as_str(), "");
         if ExternalCommands::restrict(
@@ -0,0 +1,203 @@
+use std::{
+    env::current_dir,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    process,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    internal_filesystem::internal_filesystem::InternalFileSystem,
+    repokit::interfaces::HistorySettings,
+};
+
+pub static REDACTED: &str = "<redacted>";
+
+/// The history is compacted once it grows past its maximum size by a
+/// tenth, or by a single entry for small histories
+static COMPACTION_MARGIN: usize = 10;
+
+/// Arguments matching any of these are always redacted
+static DEFAULT_REDACTIONS: [&str; 5] = [
+    "token",
+    "secret",
+    "passw(or)?d",
+    "api[-_]?key",
+    "credential",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub timestamp: u64,
+    pub command: Vec<String>,
+    pub args: Vec<String>,
+    pub cwd: String,
+    pub duration: u64,
+    pub exit_code: i32,
+}
+
+pub struct History {
+    path: PathBuf,
+    settings: HistorySettings,
+}
+
+impl HistoryEntry {
+    /// The tokens that invoke this entry's command again
+    pub fn invocation(&self) -> Vec<String> {
+        [self.command.clone(), self.args.clone()].concat()
+    }
+
+    pub fn is_redacted(&self) -> bool {
+        self.args.iter().any(|x| x.contains(REDACTED))
+    }
+}
+
+impl History {
+    pub fn new(root: &str, settings: &HistorySettings) -> History {
+        History {
+            path: InternalFileSystem::new(root)
+                .repokit_directory()
+                .join("history.jsonl"),
+            settings: settings.clone(),
+        }
+    }
+
+    /// Appends an invocation to the history. Failing to write the history
+    /// never fails the command itself
+    pub fn append(&self, command: &[&str], args: &[String], duration: Duration, exit_code: i32) {
+        if !self.settings.enabled {
+            return;
+        }
+        let entry = HistoryEntry {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|x| x.as_secs())
+                .unwrap_or_default(),
+            command: command.iter().map(|x| x.to_string()).collect(),
+            args: self.redact(args),
+            cwd: current_dir()
+                .map(|x| x.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            duration: duration.as_millis() as u64,
+            exit_code,
+        };
+        let Ok(line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        if let Some(directory) = self.path.parent() {
+            let _ = fs::create_dir_all(directory);
+        }
+        // Appending in a single write keeps concurrent runs from
+        // interleaving or overwriting each other's entries
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path);
+        if let Ok(mut file) = file {
+            let _ = file.write_all(format!("{line}\n").as_bytes());
+        }
+        self.compact();
+    }
+
+    /// Reads the most recent entries, oldest first. Malformed lines are
+    /// skipped
+    pub fn entries(&self) -> Vec<HistoryEntry> {
+        let entries: Vec<HistoryEntry> = fs::read_to_string(&self.path)
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+        let limit = self.settings.max_entries;
+        match limit {
+            0 => entries,
+            _ => entries[entries.len().saturating_sub(limit)..].to_vec(),
+        }
+    }
+
+    /// Replaces arguments matching a redaction pattern. Flags that match
+    /// have their value redacted instead
+    pub fn redact(&self, args: &[String]) -> Vec<String> {
+        let patterns: Vec<Regex> = DEFAULT_REDACTIONS
+            .iter()
+            .map(|x| x.to_string())
+            .chain(self.settings.redact.iter().cloned())
+            .filter_map(|x| RegexBuilder::new(&x).case_insensitive(true).build().ok())
+            .collect();
+        let mut redacted = Vec::new();
+        let mut redact_next = false;
+        for arg in args {
+            if redact_next {
+                redacted.push(REDACTED.to_string());
+                redact_next = false;
+                continue;
+            }
+            if !patterns.iter().any(|x| x.is_match(arg)) {
+                redacted.push(arg.clone());
+                continue;
+            }
+            match arg.split_once('=') {
+                Some((key, _)) => redacted.push(format!("{key}={REDACTED}")),
+                None if arg.starts_with('-') => {
+                    redacted.push(arg.clone());
+                    redact_next = true;
+                }
+                None => redacted.push(REDACTED.to_string()),
+            }
+        }
+        redacted
+    }
+
+    /// Formats a unix timestamp as a UTC date and time
+    pub fn format_timestamp(timestamp: u64) -> String {
+        let days = (timestamp / 86_400) as i64;
+        let seconds = timestamp % 86_400;
+        // Converts days since the epoch into a civil date
+        let shifted = days + 719_468;
+        let era = shifted.div_euclid(146_097);
+        let day_of_era = shifted.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+            seconds / 3_600,
+            seconds % 3_600 / 60,
+            seconds % 60
+        )
+    }
+
+    /// Rewrites the history down to its configured size once it grows
+    /// past it by a margin, so that it is rarely rewritten. The history is
+    /// replaced through a rename, so readers never see a partial file
+    fn compact(&self) {
+        let limit = self.settings.max_entries;
+        if limit == 0 {
+            return;
+        }
+        let contents = fs::read_to_string(&self.path).unwrap_or_default();
+        let lines: Vec<&str> = contents.lines().collect();
+        if lines.len() <= limit + (limit / COMPACTION_MARGIN).max(1) {
+            return;
+        }
+        let kept = lines[lines.len() - limit..].join("\n") + "\n";
+        let temporary = self
+            .path
+            .with_extension(format!("jsonl.{}.tmp", process::id()));
+        if fs::write(&temporary, kept).is_err() || fs::rename(&temporary, &self.path).is_err() {
+            let _ = fs::remove_file(&temporary);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::{
+    collections::HashMap,
+    path::{PathBuf, absolute},
+};
+
+use crate::{
+    docs::{markup::DocsFormat, site::Site},
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    repokit::exit_codes::ExitCodes,
+    validations::command_validations::CommandValidations,
+};
+
+pub struct DocsCommand {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+static FORMATS: [&str; 2] = ["md", "html"];
+
+impl DocsCommand {
+    pub fn new(scope: &RepoKitScope) -> DocsCommand {
+        DocsCommand {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "docs",
+                description: "Generates a static documentation site for every command",
+                args: [
+                    ("--out", "The directory to write the documentation to"),
+                    (
+                        "--format",
+                        "The format of the generated pages. Specify one of md | html. Defaults to md",
+                    ),
+                ],
+            }),
+        }
+    }
+
+    fn parse_options(args: Vec<String>) -> Result<(PathBuf, DocsFormat), String> {
+        let mut out: Option<PathBuf> = None;
+        let mut format = DocsFormat::Markdown;
+        let mut tokens = args.into_iter();
+        while let Some(token) = tokens.next() {
+            let (option, inline) = match token.split_once('=') {
+                Some((option, value)) => (option.to_string(), Some(value.to_string())),
+                None => (token.clone(), None),
+            };
+            if option != "--out" && option != "--format" {
+                return Err(format!("I don't recognize the argument \"{token}\""));
+            }
+            let value = inline
+                .or_else(|| tokens.next())
+                .ok_or(format!("The option {option} requires a value"))?;
+            match option.as_str() {
+                "--out" => out = Some(absolute(&value).unwrap_or(PathBuf::from(value))),
+                _ => format = DocsFormat::parse(&value)?,
+            }
+        }
+        let out = out.ok_or("Please specify a directory to write to using --out")?;
+        Ok((out, format))
+    }
+}
+
+impl InternalExecutable for DocsCommand {
+    fn run(
+        &self,
+        args: Vec<String>,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> i32 {
+        let (out, format) = match DocsCommand::parse_options(args) {
+            Ok(parsed) => parsed,
+            Err(error) => return Logger::error_with_code(&error, ExitCodes::USAGE),
+        };
+        Logger::info("Generating documentation");
+        let externals = CommandValidations::new(&self.scope).collect_and_validate_externals();
+        let site = Site::new(&self.scope, format, &out, internals, &externals);
+        match site.write() {
+            Ok(pages) => {
+                Logger::info(
+                    format!(
+                        "Wrote {} pages to {}",
+                        Logger::blue_bright(pages.to_string().as_str()),
+                        Logger::blue_bright(out.to_str().unwrap_or_default()),
+                    )
+                    .as_str(),
+                );
+                ExitCodes::SUCCESS
+            }
+            Err(error) => Logger::error_with_code(
+                format!("I could not write the documentation: {error}").as_str(),
+                ExitCodes::FAILURE,
+            ),
+        }
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+
+    fn complete(&self, args: &[String]) -> Vec<String> {
+        match args.last().map(|x| x.as_str()) {
+            Some("--format") => FORMATS.iter().map(|x| x.to_string()).collect(),
+            Some("--out") => Vec::new(),
+            _ => vec!["--out".to_string(), "--format".to_string()],
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::collections::HashMap;
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    repokit::{exit_codes::ExitCodes, repokit::RepoKit},
+};
+
+pub struct ExplainCommand {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+impl ExplainCommand {
+    pub fn new(scope: &RepoKitScope) -> ExplainCommand {
+        ExplainCommand {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "explain",
+                description: "Shows what a command would run, where, and with which environment, without running it. Also available as repokit --dry-run <command>",
+                args: [(
+                    "<command>",
+                    "The command to explain, followed by its subcommand and any arguments",
+                )],
+            }),
+        }
+    }
+}
+
+impl InternalExecutable for ExplainCommand {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        let Some((command, rest)) = args.split_first() else {
+            return Logger::error_with_code(
+                "Please specify a command to explain",
+                ExitCodes::USAGE,
+            );
+        };
+        let mut kit = RepoKit::from_scope(&self.scope);
+        kit.scope.options.dry_run = true;
+        kit.dispatch(command.clone(), rest.to_vec())
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+}
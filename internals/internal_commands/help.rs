@@ -8,7 +8,13 @@ use crate::{
         internal_executable_definition::InternalExecutableDefinition,
     },
     logger::logger::Logger,
-    repokit::interfaces::{CommandDefinition, RepoKitCommand, RootCommand},
+    repokit::interfaces::{
+        ArgumentDefinition, ArgumentSchema, ArgumentType, Arguments, CommandDefinition,
+        Environment, EnvironmentDefinition, EnvironmentSchema, RepoKitCommand, RootCommand,
+    },
+    validations::{
+        argument_validations::ArgumentValidations, environment_validations::EnvironmentValidations,
+    },
 };
 
 pub struct Help;
@@ -41,7 +47,9 @@ impl Help {
             Logger::blue(&command.name),
             Logger::gray(&command.description),
         );
-        Help::log_args(&command.args, None)
+        Help::log_arguments(&command.args, None);
+        Help::log_environment(&command.env, None);
+        Help::log_working_directory(&command.cwd, 6);
     }
 
     pub fn log_external_command(command: &RepoKitCommand) {
@@ -53,12 +61,24 @@ impl Help {
         );
         println!();
         Help::log_external_subcommands(&command.commands, 6);
+        if command.env.as_ref().is_some_and(|env| !env.is_empty()) {
+            println!(
+                "\n{}{}",
+                Logger::indent(Some(9)),
+                Logger::gray("Environment:")
+            );
+            Help::log_environment(&command.env, Some(9));
+        }
+        if command.cwd.is_some() {
+            println!();
+            Help::log_working_directory(&command.cwd, 9);
+        }
         if !command.owner.is_empty() {
             println!(
                 "\n{}{}{}",
                 Logger::indent(Some(9)),
                 Logger::gray("Owned by: "),
-                Logger::cyan(&command.owner),
+                Logger::cyan(&command.owner.to_string()),
             );
         }
     }
@@ -71,7 +91,9 @@ impl Help {
                 Logger::lime(format!("{}: ", name).as_str()),
                 Logger::gray(&command.description),
             );
-            Help::log_args(&command.args, Some(indentation + 3));
+            Help::log_arguments(&command.args, Some(indentation + 3));
+            Help::log_environment(&command.env, Some(indentation + 3));
+            Help::log_working_directory(&command.cwd, indentation + 3);
         }
     }
 
@@ -82,7 +104,7 @@ impl Help {
         let sorted_internals = Help::sort_internal(internals);
         Logger::space_around("Internal Commands:");
         for internal in sorted_internals {
-            Help::log_internal_command(internal.get_definition());
+            internal.help();
             println!();
         }
     }
@@ -126,10 +148,101 @@ impl Help {
         }
     }
 
+    fn log_arguments(map: &Option<Arguments>, indentation: Option<i32>) {
+        if let Some(args) = map {
+            for (key, definition) in args {
+                let description = match definition {
+                    ArgumentDefinition::Description(description) => description.clone(),
+                    ArgumentDefinition::Schema(schema) => Help::describe_schema(schema),
+                };
+                println!(
+                    "{}{}{}",
+                    Logger::indent(Some(indentation.unwrap_or(6))),
+                    Logger::green(
+                        ArgumentValidations::names(key, definition)
+                            .join(" | ")
+                            .as_str()
+                    ),
+                    Logger::gray(format!(": {}", description).as_str()),
+                );
+            }
+        }
+    }
+
+    fn log_environment(map: &Option<Environment>, indentation: Option<i32>) {
+        if let Some(env) = map {
+            for (name, definition) in env {
+                let description = match definition {
+                    EnvironmentDefinition::Description(description) => description.clone(),
+                    EnvironmentDefinition::Schema(schema) => Help::describe_environment(schema),
+                };
+                println!(
+                    "{}{}{}",
+                    Logger::indent(Some(indentation.unwrap_or(6))),
+                    Logger::cyan(format!("${name}").as_str()),
+                    Logger::gray(format!(": {}", description).as_str()),
+                );
+            }
+        }
+    }
+
+    fn log_working_directory(cwd: &Option<String>, indentation: i32) {
+        if let Some(cwd) = cwd {
+            println!(
+                "{}{}{}",
+                Logger::indent(Some(indentation)),
+                Logger::gray("Runs in: "),
+                Logger::cyan(cwd),
+            );
+        }
+    }
+
+    pub fn describe_environment(schema: &EnvironmentSchema) -> String {
+        let mut details: Vec<String> = Vec::new();
+        if !schema.values.is_empty() {
+            details.push(format!("one of {}", schema.values.join(" | ")));
+        }
+        if schema.required {
+            details.push("required".to_string());
+        }
+        if let Some(value) = EnvironmentValidations::default_value(schema) {
+            details.push(format!("defaults to {value}"));
+        }
+        if details.is_empty() {
+            return schema.description.clone();
+        }
+        format!("{} ({})", schema.description, details.join(", "))
+    }
+
+    pub fn describe_schema(schema: &ArgumentSchema) -> String {
+        let mut details: Vec<String> = Vec::new();
+        match schema.value_type {
+            ArgumentType::String => {}
+            ArgumentType::Int => details.push("integer".to_string()),
+            ArgumentType::Bool => details.push("boolean".to_string()),
+            ArgumentType::Path => details.push("path".to_string()),
+            ArgumentType::Enum => details.push(format!("one of {}", schema.values.join(" | "))),
+        }
+        if schema.required {
+            details.push("required".to_string());
+        }
+        if let Some(value) = ArgumentValidations::default_value(schema) {
+            details.push(format!("defaults to {value}"));
+        }
+        if schema.repeats {
+            details.push("repeatable".to_string());
+        }
+        if details.is_empty() {
+            return schema.description.clone();
+        }
+        format!("{} ({})", schema.description, details.join(", "))
+    }
+
     fn sort_internal(
         commands: &HashMap<String, Box<dyn InternalExecutable>>,
-    ) -> Vec<&Box<dyn InternalExecutable>> {
-        let mut vector: Vec<&Box<dyn InternalExecutable>> = commands.values().collect();
+    ) -> Vec<&dyn InternalExecutable> {
+        let mut vector: Vec<&dyn InternalExecutable> =
+            commands.values().map(|x| x.as_ref()).collect();
         sort_slice_by_str_key(&mut vector, |x| &x.get_definition().name);
         vector
     }
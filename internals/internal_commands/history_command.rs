@@ -0,0 +1,211 @@
+use std::{collections::HashMap, env::set_current_dir, path::Path, time::Duration};
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    history::history::{History, HistoryEntry},
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    output::{output::Output, records::HistoryRecord},
+    repokit::{exit_codes::ExitCodes, repokit::RepoKit},
+};
+
+pub struct HistoryCommand {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+struct HistoryOptions {
+    filter: Vec<String>,
+    limit: usize,
+    rerun: Option<usize>,
+}
+
+static DEFAULT_LIMIT: usize = 20;
+
+impl HistoryCommand {
+    pub fn new(scope: &RepoKitScope) -> HistoryCommand {
+        HistoryCommand {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "history",
+                description: "Lists the commands you've recently run, most recent first",
+                args: [
+                    (
+                        "[filter]",
+                        "Only list commands whose name or arguments contain this text",
+                    ),
+                    (
+                        "--limit",
+                        "The maximum number of entries to display. Defaults to 20. Specify 0 to display every entry",
+                    ),
+                    (
+                        "--rerun",
+                        "Runs the nth entry of the list again, where 1 is the most recent",
+                    ),
+                ],
+            }),
+        }
+    }
+
+    fn parse_options(args: Vec<String>) -> Result<HistoryOptions, String> {
+        let mut options = HistoryOptions {
+            filter: Vec::new(),
+            limit: DEFAULT_LIMIT,
+            rerun: None,
+        };
+        let mut tokens = args.into_iter();
+        while let Some(token) = tokens.next() {
+            let (option, inline) = match token.split_once('=') {
+                Some((option, value)) => (option.to_string(), Some(value.to_string())),
+                None => (token.clone(), None),
+            };
+            if option != "--limit" && option != "--rerun" {
+                options.filter.push(token);
+                continue;
+            }
+            let value = inline.or_else(|| tokens.next()).unwrap_or_default();
+            let number: usize = value.parse().map_err(|_| {
+                format!("The option {option} expects an integer but received \"{value}\"")
+            })?;
+            match option.as_str() {
+                "--limit" => options.limit = number,
+                _ => options.rerun = Some(number),
+            }
+        }
+        Ok(options)
+    }
+
+    fn rerun(&self, entries: &[(usize, &HistoryEntry)], index: usize) -> i32 {
+        let Some((_, entry)) = entries.iter().find(|(position, _)| *position == index) else {
+            return Logger::error_with_code(
+                format!(
+                    "I could not find entry {} in your history",
+                    Logger::blue_bright(index.to_string().as_str())
+                )
+                .as_str(),
+                ExitCodes::FAILURE,
+            );
+        };
+        if entry.is_redacted() {
+            return Logger::error_with_code(
+                "This entry contains redacted arguments, so I can't run it again",
+                ExitCodes::FAILURE,
+            );
+        }
+        let invocation = entry.invocation();
+        Logger::info(
+            format!(
+                "Running {}",
+                Logger::blue_bright(invocation.join(" ").as_str())
+            )
+            .as_str(),
+        );
+        if Path::new(&entry.cwd).is_dir() {
+            let _ = set_current_dir(&entry.cwd);
+        }
+        let (command, rest) = invocation.split_first().expect("invocation");
+        RepoKit::from_scope(&self.scope).dispatch(command.clone(), rest.to_vec())
+    }
+
+    fn log_entry(position: usize, entry: &HistoryEntry) {
+        let status = match entry.exit_code {
+            ExitCodes::SUCCESS => Logger::gray("succeeded"),
+            code => Logger::magenta(format!("exited {code}").as_str()),
+        };
+        println!(
+            "{}{}{}{}",
+            Logger::indent(Some(3)),
+            Logger::gray(format!("{position}. ").as_str()),
+            Logger::blue(entry.command.join(" ").as_str()),
+            Logger::green(format!(" {}", entry.args.join(" ")).trim_end()),
+        );
+        println!(
+            "{}{}{}",
+            Logger::indent(Some(6)),
+            status,
+            Logger::gray(
+                format!(
+                    " · {} · {} · {}",
+                    Logger::format_duration(Duration::from_millis(entry.duration)),
+                    History::format_timestamp(entry.timestamp),
+                    entry.cwd
+                )
+                .as_str()
+            ),
+        );
+    }
+}
+
+impl InternalExecutable for HistoryCommand {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        let options = match HistoryCommand::parse_options(args) {
+            Ok(options) => options,
+            Err(error) => return Logger::error_with_code(&error, ExitCodes::USAGE),
+        };
+        let history = History::new(&self.scope.root, &self.scope.configuration.history);
+        let all = history.entries();
+        let filter = options.filter.join(" ").to_lowercase();
+        let entries: Vec<(usize, &HistoryEntry)> = all
+            .iter()
+            .rev()
+            .filter(|entry| {
+                entry
+                    .invocation()
+                    .join(" ")
+                    .to_lowercase()
+                    .contains(&filter)
+            })
+            .enumerate()
+            .map(|(index, entry)| (index + 1, entry))
+            .collect();
+        if let Some(index) = options.rerun {
+            return self.rerun(&entries, index);
+        }
+        let shown = match options.limit {
+            0 => entries.len(),
+            limit => entries.len().min(limit),
+        };
+        if let Some(format) = self.scope.options.format {
+            let records: Vec<HistoryRecord> = entries[..shown]
+                .iter()
+                .map(|(index, entry)| HistoryRecord::from(*index, entry))
+                .collect();
+            Output::emit(format, "history", &records);
+            return ExitCodes::SUCCESS;
+        }
+        if entries.is_empty() {
+            Logger::info("No commands found in your history");
+            return ExitCodes::SUCCESS;
+        }
+        println!();
+        for (position, entry) in &entries[..shown] {
+            HistoryCommand::log_entry(*position, entry);
+        }
+        println!();
+        if shown < entries.len() {
+            Logger::info(
+                format!(
+                    "Showing the {} most recent of {} entries. Use {} to see more",
+                    Logger::blue_bright(shown.to_string().as_str()),
+                    Logger::blue_bright(entries.len().to_string().as_str()),
+                    Logger::blue_bright("--limit"),
+                )
+                .as_str(),
+            );
+        }
+        ExitCodes::SUCCESS
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+}
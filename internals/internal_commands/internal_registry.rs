@@ -5,9 +5,12 @@ use crate::{
         intenal_executable::InternalExecutable, internal_executable_definition::RepoKitScope,
     },
     internal_commands::{
-        list_commands::ListCommands, list_owners::ListOwners, locate_command::LocateCommand,
-        onboarder::Onboarder, register_command::RegisterCommand, search_commands::SearchCommands,
-        upgrade_repokit::UpgradeRepoKit,
+        docs_command::DocsCommand, explain_command::ExplainCommand,
+        history_command::HistoryCommand, list_commands::ListCommands, list_owners::ListOwners,
+        locate_command::LocateCommand, manage_cache::ManageCache, onboarder::Onboarder,
+        pick_command::PickCommand, register_command::RegisterCommand,
+        search_commands::SearchCommands, shell_completions::ShellCompletions,
+        stats_command::StatsCommand, upgrade_repokit::UpgradeRepoKit,
     },
 };
 
@@ -23,7 +26,7 @@ impl InternalRegistry {
     }
 
     pub fn get_all(&self) -> HashMap<String, Box<dyn InternalExecutable>> {
-        let internals: [Box<dyn InternalExecutable>; 7] = [
+        let internals: [Box<dyn InternalExecutable>; 14] = [
             Box::new(Onboarder::new(&self.scope)),
             Box::new(ListCommands::new(&self.scope)),
             Box::new(SearchCommands::new(&self.scope)),
@@ -31,6 +34,13 @@ impl InternalRegistry {
             Box::new(LocateCommand::new(&self.scope)),
             Box::new(RegisterCommand::new(&self.scope)),
             Box::new(UpgradeRepoKit::new(&self.scope)),
+            Box::new(ManageCache::new(&self.scope)),
+            Box::new(ShellCompletions::new(&self.scope)),
+            Box::new(PickCommand::new(&self.scope)),
+            Box::new(DocsCommand::new(&self.scope)),
+            Box::new(ExplainCommand::new(&self.scope)),
+            Box::new(HistoryCommand::new(&self.scope)),
+            Box::new(StatsCommand::new(&self.scope)),
         ];
         HashMap::from(internals.map(|x| (x.get_definition().name.to_string(), x)))
     }
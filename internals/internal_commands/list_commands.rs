@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use regex::{Regex, RegexBuilder};
+
 use crate::{
     executables::{
         intenal_executable::InternalExecutable,
@@ -9,7 +11,14 @@ use crate::{
     },
     internal_commands::help::Help,
     logger::logger::Logger,
-    repokit::interfaces::RepoKitCommand,
+    output::{
+        output::Output,
+        records::{InternalRecord, PackageRecord, RootRecord},
+    },
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{OwnerDetails, RepoKitCommand},
+    },
     validations::command_validations::CommandValidations,
 };
 
@@ -44,56 +53,108 @@ impl ListCommands {
         validators.collect_and_validate_externals()
     }
 
-    fn exit_on_invalid_scope(&self) {
-        Logger::exit_with_info(
+    /// Matches owner names exactly, or against a glob when the query
+    /// contains * or ?, ignoring case. Registry ids match the owner
+    /// they declare
+    fn owner_matcher(&self, query: &str) -> Regex {
+        let query = match self.scope.configuration.owners.get(query) {
+            Some(details) => OwnerDetails::from_registry(query, details).team,
+            None => query.to_string(),
+        };
+        let mut pattern = String::from("^");
+        for character in query.chars() {
+            match character {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                _ => pattern.push_str(&regex::escape(&character.to_string())),
+            }
+        }
+        pattern.push('$');
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .expect("escaped pattern")
+    }
+
+    fn error_on_invalid_scope(&self) -> i32 {
+        Logger::error_with_code(
             format!(
                 "Please specify a scope to list the commands of. Select one of {}",
                 Logger::blue_bright(SCOPES.join(" | ").as_str())
             )
             .as_str(),
-        );
+            ExitCodes::USAGE,
+        )
     }
 }
 
 impl InternalExecutable for ListCommands {
-    fn run(&self, args: Vec<String>, internals: &HashMap<String, Box<dyn InternalExecutable>>) {
+    fn run(
+        &self,
+        args: Vec<String>,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> i32 {
         if args.is_empty() {
-            return self.exit_on_invalid_scope();
+            return self.error_on_invalid_scope();
         }
         let query = args[0].as_str();
         let scope = &query.to_lowercase();
+        let format = self.scope.options.format;
         if scope == SCOPES[0] {
-            return Help::log_internal_commands(internals);
+            if let Some(format) = format {
+                Output::emit(format, "internal", &InternalRecord::collect(internals));
+                return ExitCodes::SUCCESS;
+            }
+            Help::log_internal_commands(internals);
+            return ExitCodes::SUCCESS;
         }
         if scope == SCOPES[2] {
-            return Help::log_root_commands(&self.scope.configuration.commands);
+            let commands = &self.scope.configuration.commands;
+            if let Some(format) = format {
+                Output::emit(format, "root", &RootRecord::collect(commands));
+                return ExitCodes::SUCCESS;
+            }
+            Help::log_root_commands(commands);
+            return ExitCodes::SUCCESS;
         }
         let registered_commands = self.collect_registered_commands();
         if scope == SCOPES[1] {
-            return Help::log_external_commands(&registered_commands);
+            if let Some(format) = format {
+                let records = PackageRecord::collect(&registered_commands);
+                Output::emit(format, "registered", &records);
+                return ExitCodes::SUCCESS;
+            }
+            Help::log_external_commands(&registered_commands);
+            return ExitCodes::SUCCESS;
         }
         let full_query = args.join(" ");
-        let full_scope = &full_query.to_lowercase();
+        let matcher = self.owner_matcher(&full_query);
         Logger::info("Searching registered commands");
         let matches: HashMap<String, RepoKitCommand> = registered_commands
             .iter()
             .filter_map(|(name, x)| {
-                if x.owner.to_lowercase().contains(full_scope) {
+                if x.owner.names().iter().any(|owner| matcher.is_match(owner)) {
                     return Some((name.clone(), x.clone()));
                 }
                 None
             })
             .collect();
+        if let Some(format) = format {
+            Output::emit(format, "registered", &PackageRecord::collect(&matches));
+            return ExitCodes::SUCCESS;
+        }
         if matches.is_empty() {
-            Logger::exit_with_info(
+            Logger::info(
                 format!(
-                    "I could not find any commands matching {}",
+                    "I could not find any commands owned by {}",
                     Logger::blue_bright(&full_query)
                 )
                 .as_str(),
             );
+            return ExitCodes::SUCCESS;
         }
         Help::log_external_commands(&matches);
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
@@ -103,4 +164,11 @@ impl InternalExecutable for ListCommands {
     fn get_definition(&self) -> &InternalExecutableDefinition {
         &self.definition
     }
+
+    fn complete(&self, args: &[String]) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        SCOPES[..3].iter().map(|scope| scope.to_string()).collect()
+    }
 }
@@ -1,8 +1,10 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
-use alphanumeric_sort::sort_str_slice;
+use alphanumeric_sort::sort_slice_by_str_key;
+use indexmap::IndexMap;
 
 use crate::{
+    code_owners::code_owners::CodeOwners,
     executables::{
         intenal_executable::InternalExecutable,
         internal_executable_definition::{
@@ -11,7 +13,14 @@ use crate::{
     },
     internal_commands::help::Help,
     logger::logger::Logger,
-    repokit::interfaces::RepoKitCommand,
+    output::{
+        output::Output,
+        records::{OwnerRecord, OwnershipRecord},
+    },
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{Owner, OwnerDetails, RepoKitCommand},
+    },
     validations::command_validations::CommandValidations,
 };
 
@@ -27,11 +36,212 @@ impl ListOwners {
             definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
                 name: "owners",
                 description: "Lists all registered command owners",
-                args: [],
+                args: [
+                    (
+                        "[name]",
+                        "The team name or registry id of an owner to display the contact details and commands of",
+                    ),
+                    (
+                        "--check",
+                        "Reports packages whose declared owner disagrees with CODEOWNERS",
+                    ),
+                ],
             }),
         }
     }
 
+    /// Collects every owner declared by a package or the owner registry
+    /// along with the packages each owns
+    fn collect_owners(&self) -> Vec<(OwnerDetails, Vec<RepoKitCommand>)> {
+        let mut owners: IndexMap<String, (OwnerDetails, Vec<RepoKitCommand>)> = IndexMap::new();
+        for (id, details) in &self.scope.configuration.owners {
+            let details = OwnerDetails::from_registry(id, details);
+            owners.insert(details.team.clone(), (details, Vec::new()));
+        }
+        let mut packages: Vec<RepoKitCommand> =
+            self.collect_registered_commands().into_values().collect();
+        sort_slice_by_str_key(&mut packages, |x| &x.name);
+        for package in packages {
+            for owner in package.owner.all() {
+                let (details, owned) = owners
+                    .entry(owner.name().to_string())
+                    .or_insert_with(|| (owner.details(), Vec::new()));
+                if let Owner::Details(declared) = owner
+                    && !details.has_contacts()
+                {
+                    *details = declared.clone();
+                }
+                owned.push(package.clone());
+            }
+        }
+        let mut owners: Vec<(OwnerDetails, Vec<RepoKitCommand>)> = owners.into_values().collect();
+        sort_slice_by_str_key(&mut owners, |(details, _)| &details.team);
+        owners
+    }
+
+    /// Displays an owner's contact details and every command they own
+    fn describe(&self, query: &str) -> i32 {
+        let registered = self
+            .scope
+            .configuration
+            .owners
+            .get(query)
+            .map(|details| OwnerDetails::from_registry(query, details).team);
+        let owner = self.collect_owners().into_iter().find(|(details, _)| {
+            registered.as_ref() == Some(&details.team)
+                || CodeOwners::agrees(query, std::slice::from_ref(&details.team))
+        });
+        let Some((details, packages)) = owner else {
+            return Logger::error_with_code(
+                format!(
+                    "I could not find an owner named {}",
+                    Logger::blue_bright(query)
+                )
+                .as_str(),
+                ExitCodes::FAILURE,
+            );
+        };
+        if let Some(format) = self.scope.options.format {
+            Output::emit(format, "owner", &[OwnerRecord::from(&details, &packages)]);
+            return ExitCodes::SUCCESS;
+        }
+        println!(
+            "\n{}{}",
+            Logger::indent(None),
+            Logger::blue_bright(&details.team)
+        );
+        let individuals = details.individuals.join(", ");
+        let contacts = [
+            (
+                "Individuals: ",
+                Some(&individuals).filter(|x| !x.is_empty()),
+            ),
+            ("Channel: ", details.channel.as_ref()),
+            ("Email: ", details.email.as_ref()),
+            ("On-call: ", details.on_call.as_ref()),
+        ];
+        for (label, value) in contacts {
+            if let Some(value) = value {
+                println!(
+                    "{}{}{}",
+                    Logger::indent(Some(8)),
+                    Logger::gray(label),
+                    Logger::cyan(value),
+                );
+            }
+        }
+        println!();
+        if packages.is_empty() {
+            Logger::info("This owner does not own any commands");
+            return ExitCodes::SUCCESS;
+        }
+        for package in &packages {
+            Help::log_external_command(package);
+            println!();
+        }
+        ExitCodes::SUCCESS
+    }
+
+    /// Compares each declared owner with the owners CODEOWNERS assigns
+    /// to the package's manifest
+    fn check(&self) -> i32 {
+        let Some(code_owners) = CodeOwners::load(&self.scope.root) else {
+            return Logger::error_with_code(
+                format!(
+                    "I could not find a CODEOWNERS file. I checked {}",
+                    Logger::blue_bright(&CodeOwners::locations())
+                )
+                .as_str(),
+                ExitCodes::FAILURE,
+            );
+        };
+        let file = code_owners
+            .path
+            .to_string_lossy()
+            .replace(&self.scope.root, "");
+        Logger::info(
+            format!(
+                "Checking declared owners against {}",
+                Logger::blue_bright(file.trim_start_matches('/'))
+            )
+            .as_str(),
+        );
+        let mut packages: Vec<RepoKitCommand> =
+            self.collect_registered_commands().into_values().collect();
+        sort_slice_by_str_key(&mut packages, |x| &x.name);
+        let mut disagreements = Vec::new();
+        for package in packages {
+            let location = package.location.replace(&self.scope.root, "");
+            let Some(rule) = code_owners.rule_for(&location) else {
+                continue;
+            };
+            let declared = package.owner.names();
+            let agrees = declared
+                .iter()
+                .any(|owner| CodeOwners::agrees(owner, &rule.owners));
+            if declared.is_empty() || agrees {
+                continue;
+            }
+            disagreements.push(OwnershipRecord {
+                package: package.name,
+                location: location.trim_start_matches('/').to_string(),
+                declared: package.owner.to_string(),
+                code_owners: rule.owners.clone(),
+                pattern: rule.pattern.clone(),
+                line: rule.line,
+            });
+        }
+        if let Some(format) = self.scope.options.format {
+            Output::emit(format, "ownership", &disagreements);
+        } else {
+            for record in &disagreements {
+                ListOwners::log_disagreement(record);
+            }
+        }
+        if disagreements.is_empty() {
+            Logger::info("Every declared owner agrees with CODEOWNERS");
+            return ExitCodes::SUCCESS;
+        }
+        let count = disagreements.len();
+        let subject = if count == 1 {
+            "package declares"
+        } else {
+            "packages declare"
+        };
+        Logger::error_with_code(
+            format!(
+                "{} {} an owner that disagrees with CODEOWNERS",
+                Logger::blue_bright(count.to_string().as_str()),
+                subject,
+            )
+            .as_str(),
+            ExitCodes::FAILURE,
+        )
+    }
+
+    fn log_disagreement(record: &OwnershipRecord) {
+        println!(
+            "\n{}{}{}",
+            Logger::indent(None),
+            Logger::blue_bright(&record.package),
+            Logger::gray(format!(": {}", record.location).as_str()),
+        );
+        println!(
+            "{}{}{}",
+            Logger::indent(Some(8)),
+            Logger::gray("Declares: "),
+            Logger::cyan(&record.declared),
+        );
+        println!(
+            "{}{}{}{}",
+            Logger::indent(Some(8)),
+            Logger::gray("CODEOWNERS: "),
+            Logger::cyan(&record.code_owners.join(" ")),
+            Logger::gray(format!(" ({} on line {})", record.pattern, record.line).as_str()),
+        );
+        println!();
+    }
+
     fn collect_registered_commands(&self) -> HashMap<String, RepoKitCommand> {
         let validators = CommandValidations::new(&self.scope);
         validators.collect_and_validate_externals()
@@ -39,29 +249,46 @@ impl ListOwners {
 }
 
 impl InternalExecutable for ListOwners {
-    fn run(&self, _: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) {
-        let registered_commands = self.collect_registered_commands();
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        if args.iter().any(|x| x == "--check") {
+            return self.check();
+        }
+        if !args.is_empty() {
+            return self.describe(&args.join(" "));
+        }
         Logger::info("Listing all command owners");
-        let mut owners: HashSet<String> = HashSet::new();
-        for (_, command) in registered_commands {
-            if !command.owner.is_empty() {
-                owners.insert(command.owner);
-            }
+        let owners = self.collect_owners();
+        if let Some(format) = self.scope.options.format {
+            let records: Vec<OwnerRecord> = owners
+                .iter()
+                .map(|(details, packages)| OwnerRecord::from(details, packages))
+                .collect();
+            Output::emit(format, "owners", &records);
+            return ExitCodes::SUCCESS;
         }
         if owners.is_empty() {
-            return Logger::exit_with_info("No owners found");
+            Logger::info("No owners found");
+            return ExitCodes::SUCCESS;
         }
         println!();
-        let mut list: Vec<String> = owners.into_iter().collect();
-        sort_str_slice(&mut list);
-        for (index, owner) in list.iter().enumerate() {
+        for (index, (details, packages)) in owners.iter().enumerate() {
+            let count: usize = packages.iter().map(|x| x.commands.len()).sum();
+            let plural_appendage = if count == 1 { "" } else { "s" };
+            let channel = details
+                .channel
+                .as_ref()
+                .map(|x| format!(" ({x})"))
+                .unwrap_or_default();
             println!(
-                "{}{}",
+                "{}{}{}{}",
                 Logger::indent(None),
-                Logger::cyan(format!("{}. {}", index + 1, &owner).as_str()),
+                Logger::cyan(format!("{}. {}", index + 1, &details.team).as_str()),
+                Logger::gray(format!(": {count} command{plural_appendage}").as_str()),
+                Logger::gray(&channel),
             );
         }
         println!();
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
@@ -71,4 +298,13 @@ impl InternalExecutable for ListOwners {
     fn get_definition(&self) -> &InternalExecutableDefinition {
         &self.definition
     }
+
+    fn complete(&self, args: &[String]) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        let mut candidates = vec!["--check".to_string()];
+        candidates.extend(self.scope.configuration.owners.keys().cloned());
+        candidates
+    }
 }
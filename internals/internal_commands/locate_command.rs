@@ -1,6 +1,7 @@
-use std::{collections::HashMap, process::exit};
+use std::collections::HashMap;
 
 use crate::{
+    configuration::configuration::Configuration,
     executables::{
         intenal_executable::InternalExecutable,
         internal_executable_definition::{
@@ -9,6 +10,8 @@ use crate::{
     },
     internal_commands::help::Help,
     logger::logger::Logger,
+    output::{output::Output, records::LocationRecord},
+    repokit::exit_codes::ExitCodes,
     validations::command_validations::CommandValidations,
 };
 
@@ -29,41 +32,59 @@ impl LocateCommand {
         }
     }
 
-    fn search_externals(&self, query: &str) {
+    fn search_externals(&self, query: &str) -> Option<String> {
         let finder = CommandValidations::new(&self.scope);
         let all = finder.collect_and_validate_externals();
-        for (_, command) in all {
-            if command.name == query {
-                Logger::log_file_path(&command.location);
-                exit(0);
-            }
+        all.into_values()
+            .find(|command| command.name == query)
+            .map(|command| command.location)
+    }
+
+    fn search_root(&self, command: &str) -> Option<String> {
+        if !self.scope.configuration.commands.contains_key(command) {
+            return None;
         }
+        let path = Configuration::path(&self.scope.root);
+        Some(path.to_str().expect("path").to_string())
     }
 
-    fn search_root(&self, command: &str) {
-        if self.scope.configuration.commands.contains_key(command) {
-            Logger::log_file_path(format!("{}/repokit.ts", &self.scope.root).as_str());
-            exit(0);
+    fn log_location(&self, name: &str, path: &str) {
+        match self.scope.options.format {
+            Some(format) => Output::emit(
+                format,
+                "location",
+                &[LocationRecord {
+                    name: name.to_string(),
+                    path: path.to_string(),
+                }],
+            ),
+            None => Logger::log_file_path(path),
         }
     }
 }
 
 impl InternalExecutable for LocateCommand {
-    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
         if args.is_empty() {
-            Logger::exit_with_info("Please specify a command to locate");
+            return Logger::error_with_code("Please specify a command to locate", ExitCodes::USAGE);
         }
         let command = &args[0];
         Logger::info(format!("Locating a command named {}", Logger::blue_bright(command)).as_str());
-        self.search_externals(command);
-        self.search_root(command);
-        Logger::exit_with_error(
-            format!(
-                "I could not find a command named {}",
-                Logger::blue_bright(command)
-            )
-            .as_str(),
-        );
+        let location = self
+            .search_externals(command)
+            .or_else(|| self.search_root(command));
+        let Some(path) = location else {
+            return Logger::error_with_code(
+                format!(
+                    "I could not find a command named {}",
+                    Logger::blue_bright(command)
+                )
+                .as_str(),
+                ExitCodes::FAILURE,
+            );
+        };
+        self.log_location(command, &path);
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
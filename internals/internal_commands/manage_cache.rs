@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use alphanumeric_sort::sort_str_slice;
+
+use crate::{
+    command_cache::command_cache::{CommandCache, ManifestStatus},
+    configuration::configuration::Configuration,
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    repokit::exit_codes::ExitCodes,
+};
+
+pub struct ManageCache {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+static ACTIONS: [&str; 2] = ["clear", "status"];
+
+impl ManageCache {
+    pub fn new(scope: &RepoKitScope) -> ManageCache {
+        ManageCache {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "cache",
+                description: "Manages the cache of parsed command definitions",
+                args: [(
+                    "<action>",
+                    format!(
+                        "The action to perform on the cache. Specify one of {}",
+                        Logger::blue(ACTIONS.join(" | ").as_str())
+                    )
+                    .as_str(),
+                )],
+            }),
+        }
+    }
+
+    fn clear(&self) {
+        let mut cache = CommandCache::new(&self.scope.root);
+        if cache.clear() {
+            return Logger::info("Cleared the command cache");
+        }
+        Logger::info("The command cache is already empty");
+    }
+
+    fn status(&self) {
+        let cache = CommandCache::new(&self.scope.root);
+        let manifests = cache.manifests();
+        Logger::info(
+            format!(
+                "Cache located at {}",
+                Logger::blue_bright(cache.path().to_str().expect("path"))
+            )
+            .as_str(),
+        );
+        Logger::info(
+            format!(
+                "Your {} file is parsed using {}",
+                Logger::blue_bright(&Configuration::file_name(&self.scope.root)),
+                Logger::blue_bright(self.scope.configuration.strategy.to_string().as_str())
+            )
+            .as_str(),
+        );
+        if manifests.is_empty() {
+            return Logger::info("The command cache is empty");
+        }
+        let total: usize = manifests.values().map(|x| x.commands.len()).sum();
+        Logger::info(
+            format!(
+                "Caching {} command{} from {} file{}\n",
+                Logger::blue_bright(total.to_string().as_str()),
+                if total == 1 { "" } else { "s" },
+                Logger::blue_bright(manifests.len().to_string().as_str()),
+                if manifests.len() == 1 { "" } else { "s" },
+            )
+            .as_str(),
+        );
+        let mut paths: Vec<&String> = manifests.keys().collect();
+        sort_str_slice(&mut paths);
+        for path in paths {
+            let parser = manifests.get(path).expect("exists").parser;
+            let status = match cache.status(path) {
+                ManifestStatus::Fresh => Logger::green("fresh"),
+                ManifestStatus::Stale => Logger::lime("stale"),
+                ManifestStatus::Missing => Logger::gray("missing"),
+            };
+            println!(
+                "{}{} {} {}",
+                Logger::indent(Some(3)),
+                Logger::blue(path),
+                status,
+                Logger::gray(format!("parsed using {parser}").as_str()),
+            );
+        }
+        println!();
+    }
+
+    fn error_on_invalid_action(&self) -> i32 {
+        Logger::error_with_code(
+            format!(
+                "Please specify an action to perform. Select one of {}",
+                Logger::blue_bright(ACTIONS.join(" | ").as_str())
+            )
+            .as_str(),
+            ExitCodes::USAGE,
+        )
+    }
+}
+
+impl InternalExecutable for ManageCache {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        if args.is_empty() {
+            return self.error_on_invalid_action();
+        }
+        let action = args[0].to_lowercase();
+        if action == ACTIONS[0] {
+            self.clear();
+            return ExitCodes::SUCCESS;
+        }
+        if action == ACTIONS[1] {
+            self.status();
+            return ExitCodes::SUCCESS;
+        }
+        self.error_on_invalid_action()
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+
+    fn complete(&self, args: &[String]) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        ACTIONS.map(String::from).to_vec()
+    }
+}
@@ -1,10 +1,17 @@
+pub mod docs_command;
+pub mod explain_command;
 pub mod help;
+pub mod history_command;
 pub mod internal_registry;
 pub mod list_commands;
 pub mod list_owners;
 pub mod locate_command;
+pub mod manage_cache;
 pub mod onboarder;
+pub mod pick_command;
 pub mod register_command;
 pub mod search_commands;
+pub mod shell_completions;
+pub mod stats_command;
 pub mod typescript_command;
 pub mod upgrade_repokit;
@@ -9,17 +9,16 @@ use crate::{
     },
     internal_commands::help::Help,
     logger::logger::Logger,
+    repokit::exit_codes::ExitCodes,
 };
 
 pub struct Onboarder {
-    pub scope: RepoKitScope,
     pub definition: InternalExecutableDefinition,
 }
 
 impl Onboarder {
-    pub fn new(scope: &RepoKitScope) -> Onboarder {
+    pub fn new(_: &RepoKitScope) -> Onboarder {
         Onboarder {
-            scope: scope.clone(),
             definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
                 name: "onboard",
                 description: "Onboarding instructions for first time users",
@@ -30,7 +29,7 @@ impl Onboarder {
 }
 
 impl InternalExecutable for Onboarder {
-    fn run(&self, _: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) {
+    fn run(&self, _: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
         Logger::info(format!("Welcome to {}", Logger::blue_bright("Repokit")).as_str());
         Logger::info(
             "Repokit is a tool designed to self-document and publish developer facing workflows in a single CLI",
@@ -53,6 +52,7 @@ impl InternalExecutable for Onboarder {
             format!("As your codebase grows, your {} CLI will continue to track all of the published workflows created by your team", Logger::blue_bright("Repokit")).as_str()
         );
         Logger::space_around("It's your living source of knowledge and documentation");
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
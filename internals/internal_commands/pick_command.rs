@@ -0,0 +1,143 @@
+use std::{
+    collections::HashMap,
+    io::{IsTerminal, Write, stdin, stdout},
+};
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    picker::picker::Picker,
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{ArgumentDefinition, ArgumentKind, ArgumentType},
+        repokit::RepoKit,
+    },
+    search::search_document::SearchDocument,
+    validations::{
+        argument_validations::ArgumentValidations, command_validations::CommandValidations,
+    },
+};
+
+pub struct PickCommand {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+pub static PICK_ALIAS: &str = "-i";
+
+impl PickCommand {
+    pub fn new(scope: &RepoKitScope) -> PickCommand {
+        PickCommand {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "pick",
+                description: "Interactively search for and run a command. Also available as repokit -i",
+                args: [(
+                    "[query]",
+                    "An optional query to begin filtering commands with",
+                )],
+            }),
+        }
+    }
+
+    fn log_plain(&self, documents: Vec<&SearchDocument>) {
+        for document in documents {
+            println!("{}\t{}", document.name, document.description);
+        }
+    }
+
+    /// Prompts for each required argument that does not define a default
+    fn prompt_arguments(&self, document: &SearchDocument) -> Vec<String> {
+        let mut argv = Vec::new();
+        let Some(args) = &document.arguments else {
+            return argv;
+        };
+        for (key, definition) in args {
+            let ArgumentDefinition::Schema(schema) = definition else {
+                continue;
+            };
+            if !schema.required || ArgumentValidations::default_value(schema).is_some() {
+                continue;
+            }
+            let names = ArgumentValidations::names(key, definition);
+            let display = names.first().cloned().unwrap_or(key.clone());
+            print!(
+                "{}{}{} ",
+                Logger::indent(Some(3)),
+                Logger::green(&display),
+                Logger::gray(format!(" ({}):", schema.description).as_str())
+            );
+            let _ = stdout().flush();
+            let mut value = String::new();
+            if stdin().read_line(&mut value).is_err() {
+                break;
+            }
+            let value = value.trim().to_string();
+            match (
+                ArgumentValidations::kind(key, definition),
+                schema.value_type,
+            ) {
+                (ArgumentKind::Positional, _) => argv.push(value),
+                (ArgumentKind::Flag, ArgumentType::Bool) => {
+                    if matches!(value.as_str(), "true" | "1" | "yes" | "y") {
+                        argv.push(display);
+                    }
+                }
+                (ArgumentKind::Flag, _) => {
+                    argv.push(display);
+                    argv.push(value);
+                }
+            }
+        }
+        argv
+    }
+}
+
+impl InternalExecutable for PickCommand {
+    fn run(
+        &self,
+        args: Vec<String>,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> i32 {
+        let externals = CommandValidations::new(&self.scope).collect_and_validate_externals();
+        let documents: Vec<SearchDocument> =
+            SearchDocument::collect(&self.scope, internals, &externals)
+                .into_iter()
+                .filter(|document| document.name != self.definition.name)
+                .collect();
+        let picker = Picker::new(&documents, &args.join(" "));
+        if !stdout().is_terminal() || !stdin().is_terminal() {
+            self.log_plain(picker.matches());
+            return ExitCodes::SUCCESS;
+        }
+        let selection = match picker.pick() {
+            Ok(Some(selection)) => selection,
+            Ok(None) => return ExitCodes::INTERRUPTED,
+            Err(error) => {
+                return Logger::error_with_code(
+                    format!("I could not start the interactive picker: {error}").as_str(),
+                    ExitCodes::FAILURE,
+                );
+            }
+        };
+        Logger::info(format!("Running {}", Logger::blue_bright(&selection.name)).as_str());
+        let arguments = self.prompt_arguments(selection);
+        let (command, rest) = selection.invocation.split_first().expect("invocation");
+        RepoKit::from_scope(&self.scope)
+            .dispatch(command.clone(), [rest, arguments.as_slice()].concat())
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+}
@@ -4,7 +4,6 @@ use std::{
     fs::{File, create_dir_all},
     io,
     path::{Path, PathBuf},
-    process,
 };
 
 use crate::{
@@ -17,6 +16,7 @@ use crate::{
     internal_commands::help::Help,
     internal_filesystem::internal_filesystem::InternalFileSystem,
     logger::logger::Logger,
+    repokit::exit_codes::ExitCodes,
 };
 
 pub struct RegisterCommand {
@@ -39,13 +39,13 @@ impl RegisterCommand {
         }
     }
 
-    fn validate_path(&self, args: Vec<String>) -> PathBuf {
+    fn validate_path(&self, args: Vec<String>) -> Result<PathBuf, i32> {
         if args.is_empty() {
-            RegisterCommand::exit_on_missing_path();
+            return Err(RegisterCommand::error_on_missing_path());
         }
         let path_arg = args[0].clone();
         if path_arg.is_empty() {
-            RegisterCommand::exit_on_missing_path();
+            return Err(RegisterCommand::error_on_missing_path());
         }
         let path = Path::new(&self.scope.root).join(&path_arg).normalize();
         if !path.exists() {
@@ -59,7 +59,7 @@ impl RegisterCommand {
             create_dir_all(&path).expect("");
         }
         if !path.is_dir() {
-            RegisterCommand::exit_on_missing_path();
+            return Err(RegisterCommand::error_on_missing_path());
         }
         let command_path = &path.join("Commands.ts");
         if command_path.exists() {
@@ -74,22 +74,26 @@ impl RegisterCommand {
                 "You can append additional commands to the existing {} instance or export another one",
                 Logger::blue_bright("RepoKitCommand")
             ).as_str());
-            process::exit(0);
+            return Err(ExitCodes::FAILURE);
         }
-        command_path.clone()
+        Ok(command_path.clone())
     }
 
-    fn exit_on_missing_path() {
-        Logger::exit_with_error(
+    fn error_on_missing_path() -> i32 {
+        Logger::error_with_code(
             "Please specify a path to a directory relative to the root of your repository",
-        );
+            ExitCodes::USAGE,
+        )
     }
 }
 
 impl InternalExecutable for RegisterCommand {
-    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
         Logger::info("Registering a new command");
-        let command_path = self.validate_path(args);
+        let command_path = match self.validate_path(args) {
+            Ok(path) => path,
+            Err(code) => return code,
+        };
         let template_path =
             InternalFileSystem::new(&self.scope.root).resolve_template("command_template.ts");
         let mut source = File::open(template_path).expect("Template");
@@ -99,6 +103,7 @@ impl InternalExecutable for RegisterCommand {
         Logger::info("Creating command file");
         Logger::info("Please fill out your command file located at:");
         Logger::log_file_path(command_path.to_str().expect("path"));
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
@@ -1,6 +1,7 @@
-use alphanumeric_sort::sort_slice_by_str_key;
 use std::collections::HashMap;
 
+use colored::ColoredString;
+
 use crate::{
     executables::{
         intenal_executable::InternalExecutable,
@@ -10,7 +11,12 @@ use crate::{
     },
     internal_commands::help::Help,
     logger::logger::Logger,
-    repokit::interfaces::{CommandDefinition, RepoKitCommand},
+    output::{output::Output, records::SearchRecord},
+    repokit::exit_codes::ExitCodes,
+    search::{
+        query::{Matcher, Query},
+        search_document::{SearchDocument, SearchSource},
+    },
     validations::command_validations::CommandValidations,
 };
 
@@ -19,6 +25,8 @@ pub struct SearchCommands {
     pub definition: InternalExecutableDefinition,
 }
 
+static DEFAULT_LIMIT: usize = 10;
+
 impl SearchCommands {
     pub fn new(scope: &RepoKitScope) -> SearchCommands {
         SearchCommands {
@@ -26,160 +34,197 @@ impl SearchCommands {
             definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
                 name: "search",
                 description: "Retrieve commands that match any search query",
-                args: [(
-                    "<query>",
-                    "A search string to match against command names, descriptions, arguments, or owner",
-                )],
+                args: [
+                    (
+                        "<query>",
+                        "A search string to match against command names, descriptions, arguments, or owner. Narrow a term to a field using owner:, path:, name:, cmd:, arg:, or tag:, combine terms using AND, OR, and NOT, and match phrases or /regular expressions/",
+                    ),
+                    (
+                        "--limit",
+                        "The maximum number of results to display. Defaults to 10. Specify 0 to display every result",
+                    ),
+                ],
             }),
         }
     }
 
-    fn search_internal(&self, query: &str, command: &Box<dyn InternalExecutable>) -> bool {
-        let config = command.get_definition();
-        if config.name.to_lowercase().contains(query) {
-            return true;
-        }
-        if config.description.to_lowercase().contains(query) {
-            return true;
-        }
-        if let Some(args) = &config.args {
-            for (arg, description) in args {
-                if arg.to_lowercase().contains(query) || description.to_lowercase().contains(query)
-                {
-                    return true;
+    fn parse_limit(args: Vec<String>) -> Result<(Vec<String>, usize), String> {
+        let mut limit = DEFAULT_LIMIT;
+        let mut query = Vec::new();
+        let mut tokens = args.into_iter();
+        while let Some(token) = tokens.next() {
+            let value = match token.split_once('=') {
+                Some(("--limit", value)) => value.to_string(),
+                _ if token == "--limit" => tokens.next().unwrap_or_default(),
+                _ => {
+                    query.push(token);
+                    continue;
                 }
-            }
+            };
+            limit = value.parse().map_err(|_| {
+                format!("The option --limit expects an integer but received \"{value}\"")
+            })?;
         }
-
-        false
+        Ok((query, limit))
     }
 
-    fn search_external(&self, query: &str, command: &RepoKitCommand) -> bool {
-        if command.name.to_lowercase().contains(query) {
-            return true;
-        }
-        if command.owner.to_lowercase().contains(query) {
-            return true;
-        }
-        if command
-            .location
-            .replace(self.scope.root.as_str(), "")
-            .to_lowercase()
-            .contains(query)
-        {
-            return true;
-        }
-        if command.description.to_lowercase().contains(query) {
-            return true;
-        }
-        for (arg, sub_command) in &command.commands {
-            if arg.to_lowercase().contains(query) || self.search_command(query, sub_command) {
-                return true;
+    fn log_result(&self, document: &SearchDocument, highlights: &[&Matcher]) {
+        let source = match document.source {
+            SearchSource::Internal => " (internal)",
+            SearchSource::Root => " (root)",
+            SearchSource::Registered => "",
+        };
+        println!(
+            "{}{}{}{}",
+            Logger::indent(Some(3)),
+            SearchCommands::highlight(&document.name, highlights, Logger::blue),
+            Logger::gray(source),
+            SearchCommands::highlight(
+                &format!(": {}", document.description),
+                highlights,
+                Logger::gray
+            ),
+        );
+        for line in document.command.iter().flat_map(|x| x.lines()) {
+            println!(
+                "{}{}",
+                Logger::indent(Some(6)),
+                SearchCommands::highlight(line, highlights, Logger::green_bright)
+            );
+        }
+        for (names, description) in &document.args {
+            let matched = highlights
+                .iter()
+                .any(|x| !x.find(names).is_empty() || !x.find(description).is_empty());
+            if matched {
+                println!(
+                    "{}{}{}",
+                    Logger::indent(Some(6)),
+                    SearchCommands::highlight(names, highlights, Logger::green),
+                    SearchCommands::highlight(
+                        &format!(": {description}"),
+                        highlights,
+                        Logger::gray
+                    ),
+                );
             }
         }
-        false
+        if !document.tags.is_empty() {
+            println!(
+                "{}{}{}",
+                Logger::indent(Some(6)),
+                Logger::gray("Tags: "),
+                SearchCommands::highlight(&document.tags.join(", "), highlights, Logger::cyan),
+            );
+        }
+        if let Some(owner) = &document.owner {
+            println!(
+                "{}{}{}",
+                Logger::indent(Some(6)),
+                Logger::gray("Owned by: "),
+                SearchCommands::highlight(owner, highlights, Logger::cyan),
+            );
+        }
+        if let Some(location) = &document.location {
+            println!(
+                "{}{}{}",
+                Logger::indent(Some(6)),
+                Logger::gray("Defined in: "),
+                SearchCommands::highlight(location, highlights, Logger::gray),
+            );
+        }
+        println!();
     }
 
-    fn search_command(&self, query: &str, command: &CommandDefinition) -> bool {
-        if command.command.to_lowercase().contains(query)
-            || command.description.to_lowercase().contains(query)
-        {
-            return true;
-        }
-        false
-    }
-
-    fn log_root_results(&self, root_results: &HashMap<String, CommandDefinition>) {
-        let total = root_results.len();
-        let plural_appendage = if total == 1 { "" } else { "s" };
-        if !root_results.is_empty() {
-            Help::log_root_commands(root_results);
+    /// Paints the text, emphasizing each range matched by the query
+    fn highlight(text: &str, highlights: &[&Matcher], paint: fn(&str) -> ColoredString) -> String {
+        let mut ranges: Vec<(usize, usize)> =
+            highlights.iter().flat_map(|x| x.find(text)).collect();
+        ranges.sort();
+        let mut output = String::new();
+        let mut cursor = 0;
+        for (start, end) in ranges {
+            if end <= cursor {
+                continue;
+            }
+            let start = start.max(cursor);
+            output.push_str(&paint(&text[cursor..start]).to_string());
+            output.push_str(&Logger::magenta_bright(&text[start..end]).to_string());
+            cursor = end;
         }
-        Logger::info(
-            format!(
-                "Matched {} command{} in your repokit config",
-                Logger::blue_bright(total.to_string().as_str()),
-                plural_appendage,
-            )
-            .as_str(),
-        );
+        output.push_str(&paint(&text[cursor..]).to_string());
+        output
     }
+}
 
-    fn log_internal_results(
+impl InternalExecutable for SearchCommands {
+    fn run(
         &self,
-        internal_results: &HashMap<String, &Box<dyn InternalExecutable>>,
-    ) {
-        let total = internal_results.len();
-        let plural_appendage = if total == 1 { "" } else { "s" };
-        if !internal_results.is_empty() {
-            let mut sorted_internals: Vec<&&Box<dyn InternalExecutable>> =
-                internal_results.values().collect();
-            sort_slice_by_str_key(&mut sorted_internals, |x| &x.get_definition().name);
-            Logger::space_around("Internal Commands:");
-            for internal in sorted_internals {
-                internal.help();
-                println!();
+        args: Vec<String>,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> i32 {
+        Logger::info("Searching commands");
+        let (query, limit) = match SearchCommands::parse_limit(args) {
+            Ok(parsed) => parsed,
+            Err(error) => return Logger::error_with_code(&error, ExitCodes::USAGE),
+        };
+        if query.is_empty() {
+            return Logger::error_with_code(
+                "Please specify a search string to query with",
+                ExitCodes::USAGE,
+            );
+        }
+        let query = match Query::from_args(&query) {
+            Ok(query) => query,
+            Err(error) => return Logger::error_with_code(&error, ExitCodes::USAGE),
+        };
+        let externals = CommandValidations::new(&self.scope).collect_and_validate_externals();
+        let mut results: Vec<(u32, SearchDocument)> =
+            SearchDocument::collect(&self.scope, internals, &externals)
+                .into_iter()
+                .filter_map(|document| Some((query.score(&document)?, document)))
+                .collect();
+        let format = self.scope.options.format;
+        if results.is_empty() && format.is_none() {
+            Logger::info("No matched commands");
+            return ExitCodes::SUCCESS;
+        }
+        results.sort_by(|(a, x), (b, y)| b.cmp(a).then_with(|| x.name.cmp(&y.name)));
+        let total = results.len();
+        let shown = if limit == 0 { total } else { total.min(limit) };
+        if let Some(format) = format {
+            let records: Vec<SearchRecord> = results[..shown]
+                .iter()
+                .map(|(score, document)| SearchRecord::from(*score, document))
+                .collect();
+            Output::emit(format, "search", &records);
+        } else {
+            let highlights = query.highlights();
+            println!();
+            for (_, document) in &results[..shown] {
+                self.log_result(document, &highlights);
             }
         }
-        Logger::info(
-            format!(
-                "Matched {} internal command{}",
-                Logger::blue_bright(total.to_string().as_str()),
-                plural_appendage,
-            )
-            .as_str(),
-        );
-    }
-
-    fn log_external_results(&self, external_commands: &HashMap<String, RepoKitCommand>) {
-        let total = external_commands.len();
         let plural_appendage = if total == 1 { "" } else { "s" };
-        if !external_commands.is_empty() {
-            Help::log_external_commands(external_commands);
-        }
         Logger::info(
             format!(
-                "Matched {} registered command{}",
+                "Matched {} command{}",
                 Logger::blue_bright(total.to_string().as_str()),
                 plural_appendage,
             )
             .as_str(),
         );
-    }
-}
-
-impl InternalExecutable for SearchCommands {
-    fn run(&self, args: Vec<String>, internals: &HashMap<String, Box<dyn InternalExecutable>>) {
-        Logger::info("Searching commands");
-        if args.is_empty() {
-            Logger::exit_with_error("Please specify a search string to query with");
-        }
-        let query = args.join(" ").to_lowercase();
-        let externals = CommandValidations::new(&self.scope).collect_and_validate_externals();
-        let mut root_results: HashMap<String, CommandDefinition> = HashMap::new();
-        let mut internal_results: HashMap<String, &Box<dyn InternalExecutable>> = HashMap::new();
-        let mut external_results: HashMap<String, RepoKitCommand> = HashMap::new();
-        for (command, script) in &self.scope.configuration.commands {
-            if self.search_command(&query, script) {
-                root_results.insert(command.clone(), script.clone());
-            }
-        }
-        for (name, command) in internals {
-            if self.search_internal(&query, command) {
-                internal_results.insert(name.clone(), command);
-            }
-        }
-        for (name, command) in externals {
-            if self.search_external(&query, &command) {
-                external_results.insert(name, command);
-            }
-        }
-        if root_results.is_empty() && internal_results.is_empty() && external_results.is_empty() {
-            Logger::exit_with_info("No matched commands");
-        }
-        self.log_root_results(&root_results);
-        self.log_internal_results(&internal_results);
-        self.log_external_results(&external_results);
+        if shown < total {
+            Logger::info(
+                format!(
+                    "Showing the top {}. Use {} to see more",
+                    Logger::blue_bright(shown.to_string().as_str()),
+                    Logger::blue_bright("--limit"),
+                )
+                .as_str(),
+            );
+        }
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
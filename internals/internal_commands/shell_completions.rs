@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use alphanumeric_sort::sort_str_slice;
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{ArgumentKind, Arguments, RepoKitCommand},
+    },
+    validations::argument_validations::ArgumentValidations,
+};
+
+pub struct ShellCompletions {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+pub static COMPLETION_ENTRY: &str = "__complete";
+
+static SHELLS: [&str; 3] = ["bash", "zsh", "fish"];
+
+static BASH: &str = r#"_repokit() {
+    local cur words cword
+    if declare -F _get_comp_words_by_ref >/dev/null; then
+        _get_comp_words_by_ref -n : cur words cword
+    else
+        cur="${COMP_WORDS[COMP_CWORD]}"
+        words=("${COMP_WORDS[@]}")
+        cword=$COMP_CWORD
+    fi
+    local IFS=$'\n'
+    COMPREPLY=($(repokit __complete "${words[@]:1:cword}" 2>/dev/null))
+    if declare -F __ltrim_colon_completions >/dev/null; then
+        __ltrim_colon_completions "$cur"
+    fi
+}
+complete -o default -F _repokit repokit
+"#;
+
+static ZSH: &str = r#"#compdef repokit
+_repokit() {
+    local -a candidates
+    candidates=(${(f)"$(repokit __complete "${(@)words[2,CURRENT]}" 2>/dev/null)"})
+    (( ${#candidates} )) && compadd -a candidates
+}
+compdef _repokit repokit
+"#;
+
+static FISH: &str = r#"function __repokit_complete
+    set -l tokens (commandline -opc)
+    set -e tokens[1]
+    repokit __complete $tokens (commandline -ct) 2>/dev/null
+end
+complete -c repokit -f -a '(__repokit_complete)'
+"#;
+
+impl ShellCompletions {
+    pub fn new(scope: &RepoKitScope) -> ShellCompletions {
+        ShellCompletions {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "completions",
+                description: "Prints a shell completion script for repokit",
+                args: [(
+                    "<shell>",
+                    format!(
+                        "The shell to generate completions for. Specify one of {}",
+                        Logger::blue(SHELLS.join(" | ").as_str())
+                    )
+                    .as_str(),
+                )],
+            }),
+        }
+    }
+
+    pub fn complete(
+        &self,
+        words: &[String],
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+        externals: &HashMap<String, RepoKitCommand>,
+    ) -> Vec<String> {
+        let (current, preceding) = match words.split_last() {
+            Some((current, preceding)) => (current.as_str(), preceding),
+            None => ("", &[] as &[String]),
+        };
+        let mut candidates = self.candidates(preceding, internals, externals);
+        candidates.retain(|candidate| candidate.starts_with(current));
+        sort_str_slice(&mut candidates);
+        candidates.dedup();
+        candidates
+    }
+
+    fn candidates(
+        &self,
+        preceding: &[String],
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+        externals: &HashMap<String, RepoKitCommand>,
+    ) -> Vec<String> {
+        let root_commands = &self.scope.configuration.commands;
+        let Some(command) = preceding.first() else {
+            return internals
+                .keys()
+                .chain(root_commands.keys())
+                .chain(externals.keys())
+                .cloned()
+                .collect();
+        };
+        if let Some(internal) = internals.get(command) {
+            return internal.complete(&preceding[1..]);
+        }
+        if let Some(root_command) = root_commands.get(command) {
+            return ShellCompletions::flags(&root_command.args);
+        }
+        let Some(external) = externals.get(command) else {
+            return Vec::new();
+        };
+        let Some(sub_command) = preceding.get(1) else {
+            return external.commands.keys().cloned().collect();
+        };
+        match external.commands.get(sub_command) {
+            Some(definition) => ShellCompletions::flags(&definition.args),
+            None => Vec::new(),
+        }
+    }
+
+    fn flags(args: &Option<Arguments>) -> Vec<String> {
+        let Some(args) = args else {
+            return Vec::new();
+        };
+        args.iter()
+            .filter(|(key, definition)| {
+                ArgumentValidations::kind(key, definition) == ArgumentKind::Flag
+            })
+            .flat_map(|(key, definition)| ArgumentValidations::names(key, definition))
+            .collect()
+    }
+}
+
+impl InternalExecutable for ShellCompletions {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        let script = match args.first().map(|shell| shell.to_lowercase()).as_deref() {
+            Some("bash") => BASH,
+            Some("zsh") => ZSH,
+            Some("fish") => FISH,
+            _ => {
+                return Logger::error_with_code(
+                    format!(
+                        "Please specify a shell to generate completions for. Select one of {}",
+                        Logger::blue_bright(SHELLS.join(" | ").as_str())
+                    )
+                    .as_str(),
+                    ExitCodes::USAGE,
+                );
+            }
+        };
+        print!("{script}");
+        ExitCodes::SUCCESS
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+
+    fn complete(&self, args: &[String]) -> Vec<String> {
+        if !args.is_empty() {
+            return Vec::new();
+        }
+        SHELLS.map(String::from).to_vec()
+    }
+}
@@ -0,0 +1,163 @@
+use std::{cmp::Reverse, collections::HashMap, time::Duration};
+
+use indexmap::IndexMap;
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{
+            InternalExecutableDefinition, InternalExecutableDefinitionInput, RepoKitScope,
+        },
+    },
+    history::history::History,
+    internal_commands::help::Help,
+    logger::logger::Logger,
+    output::{output::Output, records::UsageRecord},
+    repokit::exit_codes::ExitCodes,
+};
+
+pub struct StatsCommand {
+    pub scope: RepoKitScope,
+    pub definition: InternalExecutableDefinition,
+}
+
+static DEFAULT_LIMIT: usize = 5;
+
+impl StatsCommand {
+    pub fn new(scope: &RepoKitScope) -> StatsCommand {
+        StatsCommand {
+            scope: scope.clone(),
+            definition: InternalExecutableDefinition::define(InternalExecutableDefinitionInput {
+                name: "stats",
+                description: "Summarizes your history by the most used, slowest, and most failing commands",
+                args: [(
+                    "--limit",
+                    "The number of commands to display in each summary. Defaults to 5",
+                )],
+            }),
+        }
+    }
+
+    fn parse_limit(args: &[String]) -> Result<usize, String> {
+        let mut tokens = args.iter();
+        while let Some(token) = tokens.next() {
+            let value = match token.split_once('=') {
+                Some(("--limit", value)) => value.to_string(),
+                _ if token == "--limit" => tokens.next().cloned().unwrap_or_default(),
+                _ => continue,
+            };
+            return value.parse().map_err(|_| {
+                format!("The option --limit expects an integer but received \"{value}\"")
+            });
+        }
+        Ok(DEFAULT_LIMIT)
+    }
+
+    /// Aggregates the history by command, most used first
+    fn aggregate(&self) -> Vec<UsageRecord> {
+        let history = History::new(&self.scope.root, &self.scope.configuration.history);
+        let mut usage: IndexMap<String, UsageRecord> = IndexMap::new();
+        for entry in history.entries() {
+            let command = entry.command.join(" ");
+            let record = usage
+                .entry(command.clone())
+                .or_insert_with(|| UsageRecord::new(command));
+            record.runs += 1;
+            record.total_duration += entry.duration;
+            if entry.exit_code != ExitCodes::SUCCESS {
+                record.failures += 1;
+            }
+            record.last_run = record.last_run.max(entry.timestamp);
+        }
+        let mut records: Vec<UsageRecord> = usage.into_values().map(|x| x.summarize()).collect();
+        records.sort_by(|a, b| b.runs.cmp(&a.runs).then_with(|| a.command.cmp(&b.command)));
+        records
+    }
+
+    fn log_summary(
+        title: &str,
+        records: &[&UsageRecord],
+        describe: impl Fn(&UsageRecord) -> String,
+    ) {
+        if records.is_empty() {
+            return;
+        }
+        println!("{}{}", Logger::indent(Some(3)), Logger::blue_bright(title));
+        for (index, record) in records.iter().enumerate() {
+            println!(
+                "{}{}{}{}",
+                Logger::indent(Some(6)),
+                Logger::gray(format!("{}. ", index + 1).as_str()),
+                Logger::blue(&record.command),
+                Logger::gray(format!(": {}", describe(record)).as_str()),
+            );
+        }
+        println!();
+    }
+}
+
+impl InternalExecutable for StatsCommand {
+    fn run(&self, args: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
+        let limit = match StatsCommand::parse_limit(&args) {
+            Ok(limit) => limit,
+            Err(error) => return Logger::error_with_code(&error, ExitCodes::USAGE),
+        };
+        let records = self.aggregate();
+        if let Some(format) = self.scope.options.format {
+            Output::emit(format, "stats", &records);
+            return ExitCodes::SUCCESS;
+        }
+        if records.is_empty() {
+            Logger::info("No commands found in your history");
+            return ExitCodes::SUCCESS;
+        }
+        let runs: u64 = records.iter().map(|x| x.runs).sum();
+        Logger::info(
+            format!(
+                "Summarizing {} runs of {} commands",
+                Logger::blue_bright(runs.to_string().as_str()),
+                Logger::blue_bright(records.len().to_string().as_str()),
+            )
+            .as_str(),
+        );
+        println!();
+        let used: Vec<&UsageRecord> = records.iter().take(limit).collect();
+        StatsCommand::log_summary("Most used", &used, |x| {
+            let plural_appendage = if x.runs == 1 { "" } else { "s" };
+            format!("{} run{plural_appendage}", x.runs)
+        });
+        let mut slowest: Vec<&UsageRecord> = records.iter().collect();
+        slowest.sort_by_key(|x| Reverse(x.average_duration));
+        slowest.truncate(limit);
+        StatsCommand::log_summary("Slowest", &slowest, |x| {
+            format!(
+                "{} on average",
+                Logger::format_duration(Duration::from_millis(x.average_duration))
+            )
+        });
+        let mut failing: Vec<&UsageRecord> = records.iter().filter(|x| x.failures > 0).collect();
+        failing.sort_by(|a, b| {
+            b.failure_rate
+                .total_cmp(&a.failure_rate)
+                .then_with(|| b.runs.cmp(&a.runs))
+        });
+        failing.truncate(limit);
+        StatsCommand::log_summary("Highest failure rate", &failing, |x| {
+            format!(
+                "{:.0}% of {} run{} failed",
+                x.failure_rate * 100.0,
+                x.runs,
+                if x.runs == 1 { "" } else { "s" }
+            )
+        });
+        ExitCodes::SUCCESS
+    }
+
+    fn help(&self) {
+        Help::log_internal_command(&self.definition);
+    }
+
+    fn get_definition(&self) -> &InternalExecutableDefinition {
+        &self.definition
+    }
+}
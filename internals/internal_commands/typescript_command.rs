@@ -1,12 +1,17 @@
 use std::path::Path;
 
+use serde::de::DeserializeOwned;
 use serde_json::from_str;
 
 use crate::{
     configuration::configuration::Configuration,
     executor::executor::Executor,
     internal_filesystem::internal_filesystem::InternalFileSystem,
-    repokit::interfaces::{RepoKitCommand, RepoKitConfig},
+    logger::logger::Logger,
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{RepoKitCommand, RepoKitConfig},
+    },
 };
 
 pub struct TypescriptCommand {
@@ -27,8 +32,7 @@ impl TypescriptCommand {
         if stdout.is_empty() {
             Configuration::create(&self.root);
         }
-        let config: RepoKitConfig = from_str(stdout.as_str()).unwrap();
-        config
+        self.deserialize(&stdout, "your repokit.ts file")
     }
 
     pub fn parse_commands(&self, path_list: Vec<String>) -> Vec<RepoKitCommand> {
@@ -36,8 +40,18 @@ impl TypescriptCommand {
         let executable = InternalFileSystem::new(&self.root).resolve_command("parse_commands.ts");
         let stdout =
             self.execute(format!("{executable} --paths {paths} --root {}", self.root).as_str());
-        let commands: Vec<RepoKitCommand> = serde_json::from_str(&stdout).expect("parse");
-        commands
+        self.deserialize(&stdout, "your command files")
+    }
+
+    fn deserialize<T: DeserializeOwned>(&self, stdout: &str, source: &str) -> T {
+        match from_str(stdout) {
+            Ok(value) => value,
+            Err(error) => {
+                Logger::error(format!("I encountered an error while parsing {source}").as_str());
+                eprintln!("\n{error}\n");
+                Logger::exit_with_code("Please correct it and try again", ExitCodes::PARSE_FAILURE)
+            }
+        }
     }
 
     fn execute(&self, args: &str) -> String {
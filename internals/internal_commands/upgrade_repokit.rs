@@ -1,5 +1,5 @@
 use normalize_path::NormalizePath;
-use std::{collections::HashMap, path::Path, process::exit};
+use std::{collections::HashMap, path::Path};
 
 use crate::{
     executables::{
@@ -11,6 +11,7 @@ use crate::{
     executor::executor::Executor,
     internal_commands::help::Help,
     logger::logger::Logger,
+    repokit::exit_codes::ExitCodes,
 };
 
 pub struct UpgradeRepoKit {
@@ -30,7 +31,7 @@ impl UpgradeRepoKit {
         }
     }
 
-    fn get_package_manager(&self) -> &str {
+    fn get_package_manager(&self) -> Option<&str> {
         let manager_map = HashMap::from([
             ("npm", ("package-lock.json", "npm i -D")),
             ("yarn", ("yarn.lock", "yarn add -D")),
@@ -43,26 +44,29 @@ impl UpgradeRepoKit {
                 Logger::info(
                     format!("Detected {} installation", Logger::blue_bright(manager)).as_str(),
                 );
-                return command_prefix;
+                return Some(command_prefix);
             }
         }
         Logger::info("A node package manager was not detected");
         Logger::info(
             "To upgrade repokit install the latest version using the package manager of your choosing",
         );
-        exit(0);
+        None
     }
 }
 
 impl InternalExecutable for UpgradeRepoKit {
-    fn run(&self, _: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) {
+    fn run(&self, _: Vec<String>, _: &HashMap<String, Box<dyn InternalExecutable>>) -> i32 {
         Logger::info("Upgrading installation");
-        let command_prefix = self.get_package_manager();
+        let Some(command_prefix) = self.get_package_manager() else {
+            return ExitCodes::SUCCESS;
+        };
         Executor::exec(
             format!("{} @repokit/core@latest", command_prefix).as_str(),
             |cmd| cmd.current_dir(&self.scope.root),
         );
         Logger::info("Upgrade complete!");
+        ExitCodes::SUCCESS
     }
 
     fn help(&self) {
@@ -1,7 +1,7 @@
 use normalize_path::NormalizePath;
 use std::path::{Path, PathBuf};
 
-use crate::{executor::executor::Executor, logger::logger::Logger};
+use crate::{executor::executor::Executor, logger::logger::Logger, repokit::exit_codes::ExitCodes};
 
 pub struct InternalFileSystem {
     root: String,
@@ -27,18 +27,23 @@ impl InternalFileSystem {
         self.path_buf_to_str(self.templates_directory().join(file_name))
     }
 
+    pub fn repokit_directory(&self) -> PathBuf {
+        self.absolute(".repokit")
+    }
+
     pub fn find_root() -> String {
         let root = Executor::exec("echo $(git rev-parse --show-toplevel 2>/dev/null)", |cmd| {
             cmd
         });
         if root.is_empty() {
-            Logger::exit_with_info(
+            Logger::exit_with_code(
                 format!(
                     "To start using {}, please initialize your git repository by running {}",
                     Logger::blue("Repokit"),
                     Logger::green_bright("git init")
                 )
                 .as_str(),
+                ExitCodes::FAILURE,
             );
         }
         root
@@ -1,11 +1,14 @@
 use std::sync::Mutex;
-use std::{process, sync::LazyLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{process, sync::LazyLock, time::Duration};
 
 use colored::{ColoredString, Colorize, CustomColor};
 
 static REGISTERED_NAME: LazyLock<Mutex<String>> =
     LazyLock::new(|| Mutex::new("Repokit".to_string()));
 
+static DIAGNOSTICS_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
 pub struct Logger {}
 
 impl Logger {
@@ -13,7 +16,14 @@ impl Logger {
         *REGISTERED_NAME.lock().unwrap() = value.to_string();
     }
 
+    pub fn diagnostics_to_stderr() {
+        DIAGNOSTICS_TO_STDERR.store(true, Ordering::Relaxed);
+    }
+
     pub fn info(message: &str) {
+        if DIAGNOSTICS_TO_STDERR.load(Ordering::Relaxed) {
+            return eprintln!("{}{}", Logger::info_prefix(), message);
+        }
         println!("{}{}", Logger::info_prefix(), message);
     }
 
@@ -21,14 +31,14 @@ impl Logger {
         eprintln!("{}{}", Logger::error_prefix(), message);
     }
 
-    pub fn exit_with_info(message: &str) {
-        Logger::info(message);
-        process::exit(0);
+    pub fn error_with_code(message: &str, code: i32) -> i32 {
+        Logger::error(message);
+        code
     }
 
-    pub fn exit_with_error(message: &str) {
+    pub fn exit_with_code(message: &str, code: i32) -> ! {
         Logger::error(message);
-        process::exit(0);
+        process::exit(code);
     }
 
     pub fn space_around(message: &str) {
@@ -39,6 +49,19 @@ impl Logger {
         println!("\n{}{}\n", Logger::indent(None), Logger::blue_bright(path));
     }
 
+    pub fn format_duration(duration: Duration) -> String {
+        let milliseconds = duration.as_millis();
+        match milliseconds {
+            0..1_000 => format!("{milliseconds}ms"),
+            1_000..60_000 => format!("{:.1}s", milliseconds as f64 / 1_000.0),
+            _ => format!(
+                "{}m{}s",
+                milliseconds / 60_000,
+                milliseconds % 60_000 / 1_000
+            ),
+        }
+    }
+
     pub fn indent(times: Option<i32>) -> String {
         let indentation: i32 = times.unwrap_or(5);
         " ".repeat(indentation.try_into().unwrap())
@@ -72,10 +95,6 @@ impl Logger {
         message.cyan()
     }
 
-    pub fn cyan_bright(message: &str) -> ColoredString {
-        message.bright_cyan().bold()
-    }
-
     pub fn gray(message: &str) -> ColoredString {
         message.custom_color(CustomColor {
             r: 128,
@@ -1,21 +1,35 @@
+#![allow(clippy::module_inception)]
+
+use std::process;
+
 use crate::{
-    internal_commands::typescript_command::TypescriptCommand,
+    configuration::configuration::Configuration,
     internal_filesystem::internal_filesystem::InternalFileSystem, repokit::repokit::RepoKit,
 };
 
+mod code_owners;
+mod command_cache;
 mod configuration;
+mod docs;
+mod dotenv;
 mod executables;
 mod executor;
 mod external_commands;
+mod history;
 mod internal_commands;
 mod internal_filesystem;
 mod logger;
+mod output;
+mod picker;
 mod repokit;
+mod search;
+mod static_parser;
+mod suggestions;
 mod validations;
 
 fn main() {
     let root = InternalFileSystem::find_root();
-    let config = TypescriptCommand::new(&root).parse_configuration();
+    let config = Configuration::load(&root);
     let kit = RepoKit::new(root, config);
-    kit.invoke();
+    process::exit(kit.invoke());
 }
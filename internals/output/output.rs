@@ -0,0 +1,92 @@
+use serde::Serialize;
+
+use crate::{logger::logger::Logger, repokit::exit_codes::ExitCodes};
+
+/// Incremented whenever a field is renamed or removed from a record
+pub static SCHEMA_VERSION: u32 = 1;
+
+static FORMATS: [&str; 3] = ["json", "yaml", "csv"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Json,
+    Yaml,
+    Csv,
+}
+
+/// A record that can be rendered as rows of a CSV table
+pub trait Tabular {
+    fn columns() -> Vec<&'static str>;
+    fn rows(&self) -> Vec<Vec<String>>;
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope<'a, T: Serialize> {
+    schema_version: u32,
+    kind: &'a str,
+    data: &'a [T],
+}
+
+pub struct Output {}
+
+impl OutputFormat {
+    pub fn parse(value: &str) -> Result<OutputFormat, String> {
+        match value.to_lowercase().as_str() {
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "csv" => Ok(OutputFormat::Csv),
+            _ => Err(format!(
+                "The option --format expects one of {} but received \"{value}\"",
+                FORMATS.join(" | ")
+            )),
+        }
+    }
+}
+
+impl Output {
+    /// Writes the records to stdout without any prefixes or colors
+    pub fn emit<T: Serialize + Tabular>(format: OutputFormat, kind: &str, records: &[T]) {
+        let envelope = Envelope {
+            schema_version: SCHEMA_VERSION,
+            kind,
+            data: records,
+        };
+        let rendered = match format {
+            OutputFormat::Json => serde_json::to_string_pretty(&envelope)
+                .map(|json| json + "\n")
+                .map_err(|error| error.to_string()),
+            OutputFormat::Yaml => serde_yaml::to_string(&envelope).map_err(|e| e.to_string()),
+            OutputFormat::Csv => Ok(Output::csv(records)),
+        };
+        match rendered {
+            Ok(output) => print!("{output}"),
+            Err(error) => Logger::exit_with_code(
+                format!("I could not serialize the output: {error}").as_str(),
+                ExitCodes::FAILURE,
+            ),
+        }
+    }
+
+    fn csv<T: Tabular>(records: &[T]) -> String {
+        let mut header = vec!["schemaVersion".to_string()];
+        header.extend(T::columns().into_iter().map(String::from));
+        let mut rows = vec![header];
+        for row in records.iter().flat_map(|record| record.rows()) {
+            rows.push([vec![SCHEMA_VERSION.to_string()], row].concat());
+        }
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row.iter().map(|x| Output::escape(x)).collect();
+                cells.join(",") + "\n"
+            })
+            .collect()
+    }
+
+    fn escape(cell: &str) -> String {
+        if !cell.contains([',', '"', '\n', '\r']) {
+            return cell.to_string();
+        }
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    }
+}
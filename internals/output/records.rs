@@ -0,0 +1,485 @@
+use std::collections::{BTreeMap, HashMap};
+
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::{
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::InternalExecutableDefinition,
+    },
+    history::history::HistoryEntry,
+    output::output::Tabular,
+    repokit::interfaces::{
+        CommandDefinition, Environment, EnvironmentFiles, OwnerDetails, RepoKitCommand,
+    },
+    search::search_document::{SearchDocument, SearchSource},
+};
+
+#[derive(Serialize)]
+pub struct InternalRecord {
+    pub name: String,
+    pub description: String,
+    pub args: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+pub struct RootRecord {
+    pub name: String,
+    #[serde(flatten)]
+    pub definition: CommandDefinition,
+}
+
+#[derive(Serialize)]
+pub struct PackageRecord {
+    pub name: String,
+    pub owner: String,
+    pub owners: Vec<OwnerDetails>,
+    pub location: String,
+    pub description: String,
+    pub env: Option<Environment>,
+    #[serde(rename = "envFile")]
+    pub env_file: Option<EnvironmentFiles>,
+    pub cwd: Option<String>,
+    pub tags: Vec<String>,
+    pub commands: BTreeMap<String, CommandDefinition>,
+}
+
+#[derive(Serialize)]
+pub struct SearchRecord {
+    pub score: u32,
+    pub source: SearchSource,
+    pub name: String,
+    pub invocation: Vec<String>,
+    pub description: String,
+    pub command: Option<String>,
+    pub owner: Option<String>,
+    pub location: Option<String>,
+    pub args: IndexMap<String, String>,
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerRecord {
+    pub name: String,
+    pub individuals: Vec<String>,
+    pub channel: Option<String>,
+    pub email: Option<String>,
+    pub on_call: Option<String>,
+    pub commands: usize,
+    pub packages: Vec<OwnedPackageRecord>,
+}
+
+#[derive(Serialize)]
+pub struct OwnedPackageRecord {
+    pub name: String,
+    pub location: String,
+    pub commands: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipRecord {
+    pub package: String,
+    pub location: String,
+    pub declared: String,
+    pub code_owners: Vec<String>,
+    pub pattern: String,
+    pub line: usize,
+}
+
+#[derive(Serialize)]
+pub struct HistoryRecord {
+    pub index: usize,
+    #[serde(flatten)]
+    pub entry: HistoryEntry,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageRecord {
+    pub command: String,
+    pub runs: u64,
+    pub failures: u64,
+    pub failure_rate: f64,
+    pub total_duration: u64,
+    pub average_duration: u64,
+    pub last_run: u64,
+}
+
+#[derive(Serialize)]
+pub struct LocationRecord {
+    pub name: String,
+    pub path: String,
+}
+
+impl InternalRecord {
+    pub fn from(definition: &InternalExecutableDefinition) -> InternalRecord {
+        InternalRecord {
+            name: definition.name.clone(),
+            description: definition.description.clone(),
+            args: definition
+                .args
+                .clone()
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    pub fn collect(
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+    ) -> Vec<InternalRecord> {
+        let mut records: Vec<InternalRecord> = internals
+            .values()
+            .map(|internal| InternalRecord::from(internal.get_definition()))
+            .collect();
+        records.sort_by(|a, b| a.name.cmp(&b.name));
+        records
+    }
+}
+
+impl RootRecord {
+    pub fn collect(commands: &HashMap<String, CommandDefinition>) -> Vec<RootRecord> {
+        let mut records: Vec<RootRecord> = commands
+            .iter()
+            .map(|(name, definition)| RootRecord {
+                name: name.clone(),
+                definition: definition.clone(),
+            })
+            .collect();
+        records.sort_by(|a, b| a.name.cmp(&b.name));
+        records
+    }
+}
+
+impl PackageRecord {
+    pub fn collect(packages: &HashMap<String, RepoKitCommand>) -> Vec<PackageRecord> {
+        let mut records: Vec<PackageRecord> = packages
+            .values()
+            .map(|package| PackageRecord {
+                name: package.name.clone(),
+                owner: package.owner.to_string(),
+                owners: package.owner.all().iter().map(|x| x.details()).collect(),
+                location: package.location.clone(),
+                description: package.description.clone(),
+                env: package.env.clone(),
+                env_file: package.env_file.clone(),
+                cwd: package.cwd.clone(),
+                tags: package.tags.clone(),
+                commands: package.commands.clone().into_iter().collect(),
+            })
+            .collect();
+        records.sort_by(|a, b| a.name.cmp(&b.name));
+        records
+    }
+}
+
+impl OwnerRecord {
+    pub fn from(details: &OwnerDetails, packages: &[RepoKitCommand]) -> OwnerRecord {
+        OwnerRecord {
+            name: details.team.clone(),
+            individuals: details.individuals.clone(),
+            channel: details.channel.clone(),
+            email: details.email.clone(),
+            on_call: details.on_call.clone(),
+            commands: packages.iter().map(|x| x.commands.len()).sum(),
+            packages: packages
+                .iter()
+                .map(|package| {
+                    let mut commands: Vec<String> = package.commands.keys().cloned().collect();
+                    commands.sort();
+                    OwnedPackageRecord {
+                        name: package.name.clone(),
+                        location: package.location.clone(),
+                        commands,
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+impl HistoryRecord {
+    pub fn from(index: usize, entry: &HistoryEntry) -> HistoryRecord {
+        HistoryRecord {
+            index,
+            entry: entry.clone(),
+        }
+    }
+}
+
+impl UsageRecord {
+    pub fn new(command: String) -> UsageRecord {
+        UsageRecord {
+            command,
+            runs: 0,
+            failures: 0,
+            failure_rate: 0.0,
+            total_duration: 0,
+            average_duration: 0,
+            last_run: 0,
+        }
+    }
+
+    /// Derives the rates and averages from the accumulated totals
+    pub fn summarize(mut self) -> UsageRecord {
+        if self.runs > 0 {
+            self.failure_rate = self.failures as f64 / self.runs as f64;
+            self.average_duration = self.total_duration / self.runs;
+        }
+        self
+    }
+}
+
+impl SearchRecord {
+    pub fn from(score: u32, document: &SearchDocument) -> SearchRecord {
+        SearchRecord {
+            score,
+            source: document.source,
+            name: document.name.clone(),
+            invocation: document.invocation.clone(),
+            description: document.description.clone(),
+            command: document.command.clone(),
+            owner: document.owner.clone(),
+            location: document.location.clone(),
+            args: document.args.iter().cloned().collect(),
+            tags: document.tags.clone(),
+        }
+    }
+}
+
+impl Tabular for InternalRecord {
+    fn columns() -> Vec<&'static str> {
+        vec!["name", "description", "args"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let args: Vec<&str> = self.args.keys().map(|x| x.as_str()).collect();
+        vec![vec![
+            self.name.clone(),
+            self.description.clone(),
+            args.join(";"),
+        ]]
+    }
+}
+
+impl Tabular for RootRecord {
+    fn columns() -> Vec<&'static str> {
+        vec!["name", "description", "command", "args", "tags"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![[vec![self.name.clone()], definition_cells(&self.definition)].concat()]
+    }
+}
+
+impl Tabular for PackageRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "package",
+            "owner",
+            "location",
+            "name",
+            "description",
+            "command",
+            "args",
+            "tags",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        self.commands
+            .iter()
+            .map(|(name, definition)| {
+                let package = vec![
+                    self.name.clone(),
+                    self.owner.clone(),
+                    self.location.clone(),
+                    name.clone(),
+                ];
+                let mut cells = [package, definition_cells(definition)].concat();
+                let tags = [self.tags.clone(), definition.tags.clone()].concat();
+                *cells.last_mut().expect("tags") = tags.join(";");
+                cells
+            })
+            .collect()
+    }
+}
+
+impl Tabular for SearchRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "score",
+            "source",
+            "name",
+            "description",
+            "command",
+            "owner",
+            "location",
+            "args",
+            "tags",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let args: Vec<&str> = self.args.keys().map(|x| x.as_str()).collect();
+        vec![vec![
+            self.score.to_string(),
+            self.source.label().to_string(),
+            self.name.clone(),
+            self.description.clone(),
+            self.command.clone().unwrap_or_default(),
+            self.owner.clone().unwrap_or_default(),
+            self.location.clone().unwrap_or_default(),
+            args.join(";"),
+            self.tags.join(";"),
+        ]]
+    }
+}
+
+impl Tabular for OwnerRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "name",
+            "individuals",
+            "channel",
+            "email",
+            "onCall",
+            "commands",
+            "package",
+            "command",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let owner = vec![
+            self.name.clone(),
+            self.individuals.join(";"),
+            self.channel.clone().unwrap_or_default(),
+            self.email.clone().unwrap_or_default(),
+            self.on_call.clone().unwrap_or_default(),
+            self.commands.to_string(),
+        ];
+        let commands: Vec<(String, String)> = self
+            .packages
+            .iter()
+            .flat_map(|package| {
+                package
+                    .commands
+                    .iter()
+                    .map(|command| (package.name.clone(), command.clone()))
+            })
+            .collect();
+        if commands.is_empty() {
+            return vec![[owner, vec![String::new(), String::new()]].concat()];
+        }
+        commands
+            .into_iter()
+            .map(|(package, command)| [owner.clone(), vec![package, command]].concat())
+            .collect()
+    }
+}
+
+impl Tabular for OwnershipRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "package",
+            "location",
+            "declared",
+            "codeOwners",
+            "pattern",
+            "line",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.package.clone(),
+            self.location.clone(),
+            self.declared.clone(),
+            self.code_owners.join(";"),
+            self.pattern.clone(),
+            self.line.to_string(),
+        ]]
+    }
+}
+
+impl Tabular for HistoryRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "index",
+            "timestamp",
+            "command",
+            "args",
+            "cwd",
+            "duration",
+            "exitCode",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.index.to_string(),
+            self.entry.timestamp.to_string(),
+            self.entry.command.join(" "),
+            self.entry.args.join(" "),
+            self.entry.cwd.clone(),
+            self.entry.duration.to_string(),
+            self.entry.exit_code.to_string(),
+        ]]
+    }
+}
+
+impl Tabular for UsageRecord {
+    fn columns() -> Vec<&'static str> {
+        vec![
+            "command",
+            "runs",
+            "failures",
+            "failureRate",
+            "totalDuration",
+            "averageDuration",
+            "lastRun",
+        ]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![
+            self.command.clone(),
+            self.runs.to_string(),
+            self.failures.to_string(),
+            format!("{:.4}", self.failure_rate),
+            self.total_duration.to_string(),
+            self.average_duration.to_string(),
+            self.last_run.to_string(),
+        ]]
+    }
+}
+
+impl Tabular for LocationRecord {
+    fn columns() -> Vec<&'static str> {
+        vec!["name", "path"]
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        vec![vec![self.name.clone(), self.path.clone()]]
+    }
+}
+
+/// The description, command, argument names, and tags of a definition
+fn definition_cells(definition: &CommandDefinition) -> Vec<String> {
+    let args: Vec<&str> = definition
+        .args
+        .iter()
+        .flat_map(|args| args.keys())
+        .map(|x| x.as_str())
+        .collect();
+    vec![
+        definition.description.clone(),
+        definition.command_text(),
+        args.join(";"),
+        definition.tags.join(";"),
+    ]
+}
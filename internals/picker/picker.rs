@@ -0,0 +1,308 @@
+use std::io::{self, Stdout, Write, stdout};
+
+use crossterm::{
+    cursor::{Hide, MoveTo, Show},
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::Print,
+    terminal::{
+        self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode,
+        enable_raw_mode,
+    },
+};
+
+use crate::{
+    logger::logger::Logger,
+    search::search_document::{SearchDocument, SearchSource},
+};
+
+pub struct Picker<'a> {
+    documents: &'a [SearchDocument],
+    query: String,
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+    offset: usize,
+}
+
+static BOUNDARIES: [char; 6] = [' ', '-', '_', ':', '/', '.'];
+
+impl<'a> Picker<'a> {
+    pub fn new(documents: &'a [SearchDocument], query: &str) -> Picker<'a> {
+        let mut picker = Picker {
+            documents,
+            query: query.to_string(),
+            matches: Vec::new(),
+            selected: 0,
+            offset: 0,
+        };
+        picker.filter();
+        picker
+    }
+
+    /// Returns the documents matching the current query, best first
+    pub fn matches(&self) -> Vec<&SearchDocument> {
+        self.matches
+            .iter()
+            .map(|(index, _)| &self.documents[*index])
+            .collect()
+    }
+
+    /// Runs the picker until a command is selected or the picker is
+    /// dismissed
+    pub fn pick(mut self) -> io::Result<Option<&'a SearchDocument>> {
+        let mut out = stdout();
+        enable_raw_mode()?;
+        execute!(out, EnterAlternateScreen, Hide)?;
+        let selection = self.listen(&mut out);
+        execute!(out, LeaveAlternateScreen, Show)?;
+        disable_raw_mode()?;
+        Ok(selection?.map(|index| &self.documents[index]))
+    }
+
+    /// Scores a subsequence match of the query within the text, returning
+    /// the positions of each matched character
+    pub fn fuzzy(query: &str, text: &str) -> Option<(i64, Vec<usize>)> {
+        let characters: Vec<char> = text.chars().collect();
+        let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+        let mut positions = Vec::new();
+        let mut score: i64 = 0;
+        let mut cursor = 0;
+        for character in query.chars().filter(|c| !c.is_whitespace()).map(lower) {
+            let index = (cursor..characters.len()).find(|i| lower(characters[*i]) == character)?;
+            score += 16;
+            if index > 0 && positions.last() == Some(&(index - 1)) {
+                score += 8;
+            }
+            if index == 0 || BOUNDARIES.contains(&characters[index - 1]) {
+                score += 10;
+            }
+            score -= (index - cursor) as i64;
+            positions.push(index);
+            cursor = index + 1;
+        }
+        Some((score - characters.len() as i64 / 8, positions))
+    }
+
+    fn filter(&mut self) {
+        let mut scored: Vec<(i64, usize, Vec<usize>)> = self
+            .documents
+            .iter()
+            .enumerate()
+            .filter_map(|(index, document)| {
+                if let Some((score, positions)) = Picker::fuzzy(&self.query, &document.name) {
+                    return Some((score, index, positions));
+                }
+                let query = self.query.trim().to_lowercase();
+                let description = document.description.to_lowercase();
+                description
+                    .contains(&query)
+                    .then_some((query.len() as i64, index, Vec::new()))
+            })
+            .collect();
+        scored.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then_with(|| self.documents[a.1].name.cmp(&self.documents[b.1].name))
+        });
+        self.matches = scored
+            .into_iter()
+            .map(|(_, index, positions)| (index, positions))
+            .collect();
+        self.selected = 0;
+        self.offset = 0;
+    }
+
+    fn listen(&mut self, out: &mut Stdout) -> io::Result<Option<usize>> {
+        loop {
+            self.render(out)?;
+            let Event::Key(KeyEvent {
+                code,
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            }) = event::read()?
+            else {
+                continue;
+            };
+            let control = modifiers.contains(KeyModifiers::CONTROL);
+            match code {
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Char('c') if control => return Ok(None),
+                KeyCode::Enter => return Ok(self.matches.get(self.selected).map(|x| x.0)),
+                KeyCode::Up | KeyCode::BackTab => self.move_selection(-1),
+                KeyCode::Char('p') if control => self.move_selection(-1),
+                KeyCode::Down | KeyCode::Tab => self.move_selection(1),
+                KeyCode::Char('n') if control => self.move_selection(1),
+                KeyCode::Char('u') if control => {
+                    self.query.clear();
+                    self.filter();
+                }
+                KeyCode::Backspace => {
+                    self.query.pop();
+                    self.filter();
+                }
+                KeyCode::Char(character) if !control => {
+                    self.query.push(character);
+                    self.filter();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let last = self.matches.len() - 1;
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+    }
+
+    fn render(&mut self, out: &mut Stdout) -> io::Result<()> {
+        let (width, height) = terminal::size()?;
+        let width = width as usize;
+        let rows = (height as usize).saturating_sub(2).max(2);
+        let list_height = (rows / 2).max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        }
+        if self.selected >= self.offset + list_height {
+            self.offset = self.selected + 1 - list_height;
+        }
+        queue!(out, Clear(ClearType::All), MoveTo(0, 0))?;
+        let count = format!(" {}/{}", self.matches.len(), self.documents.len());
+        queue!(
+            out,
+            Print(Logger::blue_bright("> ")),
+            Print(Picker::truncate(
+                &self.query,
+                width.saturating_sub(count.len() + 2)
+            )),
+            Print(Logger::gray(&count))
+        )?;
+        let visible = self
+            .matches
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(list_height);
+        for (row, (index, (document, positions))) in visible.enumerate() {
+            let document = &self.documents[*document];
+            queue!(
+                out,
+                MoveTo(0, row as u16 + 1),
+                Print(self.render_entry(document, positions, index == self.selected, width))
+            )?;
+        }
+        let divider = list_height as u16 + 1;
+        queue!(
+            out,
+            MoveTo(0, divider),
+            Print(Logger::gray(&"─".repeat(width)))
+        )?;
+        if let Some((index, _)) = self.matches.get(self.selected) {
+            let preview = Picker::preview(&self.documents[*index], width);
+            for (row, line) in preview.into_iter().take(rows - list_height).enumerate() {
+                queue!(out, MoveTo(0, divider + 1 + row as u16), Print(line))?;
+            }
+        }
+        out.flush()
+    }
+
+    fn render_entry(
+        &self,
+        document: &SearchDocument,
+        positions: &[usize],
+        selected: bool,
+        width: usize,
+    ) -> String {
+        let mut line = match selected {
+            true => Logger::blue_bright("> ").to_string(),
+            false => "  ".to_string(),
+        };
+        let available = width.saturating_sub(2);
+        let name: Vec<char> = document.name.chars().take(available).collect();
+        for (index, character) in name.iter().enumerate() {
+            let character = character.to_string();
+            let painted = match (positions.contains(&index), selected) {
+                (true, _) => Logger::magenta_bright(&character),
+                (false, true) => Logger::blue_bright(&character),
+                (false, false) => Logger::blue(&character),
+            };
+            line.push_str(&painted.to_string());
+        }
+        let remaining = available.saturating_sub(name.len() + 2);
+        if remaining > 0 {
+            let description = Picker::truncate(&document.description, remaining);
+            line.push_str(&Logger::gray(&format!("  {description}")).to_string());
+        }
+        line
+    }
+
+    fn preview(document: &SearchDocument, width: usize) -> Vec<String> {
+        let source = match document.source {
+            SearchSource::Internal => " (internal)",
+            SearchSource::Root => " (root)",
+            SearchSource::Registered => "",
+        };
+        let mut lines = vec![
+            format!(
+                "{}{}",
+                Logger::blue_bright(&Picker::truncate(&document.name, width)),
+                Logger::gray(&Picker::truncate(
+                    source,
+                    width.saturating_sub(document.name.len())
+                ))
+            ),
+            Logger::gray(&Picker::truncate(&document.description, width)).to_string(),
+        ];
+        if let Some(command) = &document.command {
+            lines.push(String::new());
+            lines.push(Logger::green_bright(&Picker::truncate(command, width)).to_string());
+        }
+        if !document.args.is_empty() {
+            lines.push(String::new());
+            for (names, description) in &document.args {
+                let line = Picker::truncate(&format!("{names}: {description}"), width);
+                let (names, description) = match line.is_char_boundary(names.len()) {
+                    true => line.split_at(names.len()),
+                    false => (line.as_str(), ""),
+                };
+                lines.push(format!(
+                    "{}{}",
+                    Logger::green(names),
+                    Logger::gray(description)
+                ));
+            }
+        }
+        let mut details = Vec::new();
+        if let Some(owner) = &document.owner {
+            details.push(("Owned by: ", owner));
+        }
+        if let Some(location) = &document.location {
+            details.push(("Defined in: ", location));
+        }
+        if !details.is_empty() {
+            lines.push(String::new());
+        }
+        for (label, value) in details {
+            lines.push(format!(
+                "{}{}",
+                Logger::gray(label),
+                Logger::cyan(&Picker::truncate(value, width.saturating_sub(label.len())))
+            ));
+        }
+        lines
+    }
+
+    fn truncate(text: &str, width: usize) -> String {
+        let text = text.replace(['\n', '\r', '\t'], " ");
+        if text.chars().count() <= width {
+            return text;
+        }
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            truncated.push('…');
+        }
+        truncated
+    }
+}
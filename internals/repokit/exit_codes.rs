@@ -0,0 +1,29 @@
+/// Exit codes reported by repokit when a failure originates in
+/// repokit itself rather than in the command it dispatched to.
+///
+/// Commands dispatched to a child process exit with the child's
+/// own status. Children terminated by a signal exit with `128 + n`
+/// where `n` is the signal number, matching common shell behavior
+pub struct ExitCodes;
+
+impl ExitCodes {
+    /// The command completed successfully
+    pub const SUCCESS: i32 = 0;
+    /// A runtime failure, such as an I/O error, a missing git installation,
+    /// or a working directory or env file that could not be resolved
+    pub const FAILURE: i32 = 1;
+    /// The arguments provided to a command were invalid or missing
+    pub const USAGE: i32 = 2;
+    /// Two or more commands were registered under the same name
+    pub const COLLISION: i32 = 3;
+    /// A configuration or command file could not be parsed or is invalid
+    pub const PARSE_FAILURE: i32 = 4;
+    /// The command did not complete within its timeout
+    pub const TIMEOUT: i32 = 124;
+    /// The requested command or subcommand does not exist
+    pub const COMMAND_NOT_FOUND: i32 = 127;
+    /// Offset applied to signal numbers of terminated children
+    pub const SIGNAL_OFFSET: i32 = 128;
+    /// The command was interrupted, such as with Ctrl+C
+    pub const INTERRUPTED: i32 = ExitCodes::SIGNAL_OFFSET + libc::SIGINT;
+}
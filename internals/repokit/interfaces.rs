@@ -1,20 +1,369 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt, time::Duration};
 
-use serde::Deserialize;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Debug, Deserialize, Clone)]
+use crate::{executor::executor::Executor, static_parser::static_parser::ParseStrategy};
+
+pub type Arguments = IndexMap<String, ArgumentDefinition>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ArgumentDefinition {
+    Description(String),
+    Schema(ArgumentSchema),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentKind {
+    Flag,
+    Positional,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ArgumentType {
+    #[default]
+    String,
+    Int,
+    Bool,
+    Enum,
+    Path,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArgumentSchema {
+    pub description: String,
+    pub kind: Option<ArgumentKind>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+    pub default: Option<Value>,
+    #[serde(default, rename = "type")]
+    pub value_type: ArgumentType,
+    #[serde(default)]
+    pub values: Vec<String>,
+    #[serde(default)]
+    pub repeats: bool,
+}
+
+pub type Environment = IndexMap<String, EnvironmentDefinition>;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum EnvironmentDefinition {
+    Description(String),
+    Schema(EnvironmentSchema),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EnvironmentSchema {
+    pub description: String,
+    #[serde(default)]
+    pub required: bool,
+    pub default: Option<Value>,
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum EnvironmentFiles {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl EnvironmentFiles {
+    pub fn paths(&self) -> Vec<&str> {
+        match self {
+            EnvironmentFiles::One(path) => vec![path.as_str()],
+            EnvironmentFiles::Many(paths) => paths.iter().map(|x| x.as_str()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerDetails {
+    #[serde(default)]
+    pub team: String,
+    #[serde(default)]
+    pub individuals: Vec<String>,
+    pub channel: Option<String>,
+    pub email: Option<String>,
+    pub on_call: Option<String>,
+}
+
+/// A team name, a reference to the config's owner registry, or the
+/// owner's contact details
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Owner {
+    Name(String),
+    Details(OwnerDetails),
+}
+
+impl Default for Owner {
+    fn default() -> Self {
+        Owner::Name(String::new())
+    }
+}
+
+impl Owner {
+    pub fn name(&self) -> &str {
+        match self {
+            Owner::Name(name) => name,
+            Owner::Details(details) => &details.team,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.name().is_empty()
+    }
+
+    pub fn details(&self) -> OwnerDetails {
+        match self {
+            Owner::Name(name) => OwnerDetails {
+                team: name.clone(),
+                ..OwnerDetails::default()
+            },
+            Owner::Details(details) => details.clone(),
+        }
+    }
+
+    /// Replaces a reference to the owner registry with the details it
+    /// declares. Registry entries without a team are named by their id
+    pub fn resolve(&self, registry: &IndexMap<String, OwnerDetails>) -> Owner {
+        let Owner::Name(id) = self else {
+            return self.clone();
+        };
+        match registry.get(id) {
+            Some(details) => Owner::Details(OwnerDetails::from_registry(id, details)),
+            None => self.clone(),
+        }
+    }
+}
+
+/// One or more owners. Legacy definitions declare a single owner
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum Owners {
+    One(Owner),
+    Many(Vec<Owner>),
+}
+
+impl Default for Owners {
+    fn default() -> Self {
+        Owners::One(Owner::default())
+    }
+}
+
+impl Owners {
+    /// Every owner with a name
+    pub fn all(&self) -> Vec<&Owner> {
+        let owners = match self {
+            Owners::One(owner) => vec![owner],
+            Owners::Many(owners) => owners.iter().collect(),
+        };
+        owners.into_iter().filter(|x| !x.is_empty()).collect()
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.all().into_iter().map(|x| x.name()).collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.all().is_empty()
+    }
+
+    pub fn resolve(&self, registry: &IndexMap<String, OwnerDetails>) -> Owners {
+        match self {
+            Owners::One(owner) => Owners::One(owner.resolve(registry)),
+            Owners::Many(owners) => {
+                Owners::Many(owners.iter().map(|x| x.resolve(registry)).collect())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Owners {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self.names().join(", "))
+    }
+}
+
+impl OwnerDetails {
+    pub fn from_registry(id: &str, details: &OwnerDetails) -> OwnerDetails {
+        let mut details = details.clone();
+        if details.team.is_empty() {
+            details.team = id.to_string();
+        }
+        details
+    }
+
+    pub fn has_contacts(&self) -> bool {
+        !self.individuals.is_empty()
+            || self.channel.is_some()
+            || self.email.is_some()
+            || self.on_call.is_some()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum CommandLine {
+    Shell(String),
+    Argv(Vec<String>),
+}
+
+/// Either the name or path of a shell, or the shell along with its flags
+/// and whether to run in strict mode
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum ShellSetting {
+    Program(String),
+    Options(ShellOptions),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ShellOptions {
+    pub program: Option<String>,
+    pub flags: Option<Vec<String>>,
+    pub strict: Option<bool>,
+}
+
+impl ShellSetting {
+    pub fn options(&self) -> ShellOptions {
+        match self {
+            ShellSetting::Program(program) => ShellOptions {
+                program: Some(program.clone()),
+                ..ShellOptions::default()
+            },
+            ShellSetting::Options(options) => options.clone(),
+        }
+    }
+}
+
+impl Default for CommandLine {
+    fn default() -> Self {
+        CommandLine::Argv(Vec::new())
+    }
+}
+
+impl CommandLine {
+    pub fn is_empty(&self) -> bool {
+        match self {
+            CommandLine::Shell(script) => script.trim().is_empty(),
+            CommandLine::Argv(argv) => argv.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for CommandLine {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CommandLine::Shell(script) => write!(formatter, "{script}"),
+            CommandLine::Argv(argv) => write!(formatter, "{}", Executor::join("", argv).trim()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CommandDefinition {
-    pub command: String,
+    #[serde(default, skip_serializing_if = "CommandLine::is_empty")]
+    pub command: CommandLine,
+    pub script: Option<String>,
+    pub interpreter: Option<String>,
+    pub file: Option<String>,
     pub description: String,
-    pub args: Option<HashMap<String, String>>,
+    pub args: Option<Arguments>,
+    pub env: Option<Environment>,
+    #[serde(rename = "envFile")]
+    pub env_file: Option<EnvironmentFiles>,
+    pub cwd: Option<String>,
+    pub shell: Option<ShellSetting>,
+    pub timeout: Option<DurationSetting>,
+    #[serde(default)]
+    pub retries: u32,
+    #[serde(rename = "retryDelay")]
+    pub retry_delay: Option<DurationSetting>,
+    pub backoff: Option<f64>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A duration in seconds, or text such as 500ms, 30s, 5m, or 1h
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum DurationSetting {
+    Seconds(f64),
+    Text(String),
+}
+
+impl DurationSetting {
+    pub fn parse(&self) -> Result<Duration, String> {
+        let invalid = || {
+            format!(
+                "The duration {self} is invalid. Specify a number of seconds or a value such as 500ms, 30s, 5m, or 1h"
+            )
+        };
+        let seconds = match self {
+            DurationSetting::Seconds(seconds) => *seconds,
+            DurationSetting::Text(text) => {
+                let text = text.trim();
+                let split = text
+                    .find(|x: char| !x.is_ascii_digit() && x != '.')
+                    .unwrap_or(text.len());
+                let (value, unit) = text.split_at(split);
+                let value: f64 = value.parse().map_err(|_| invalid())?;
+                let scale = match unit.trim() {
+                    "ms" => 0.001,
+                    "" | "s" => 1.0,
+                    "m" => 60.0,
+                    "h" => 3_600.0,
+                    _ => return Err(invalid()),
+                };
+                value * scale
+            }
+        };
+        Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+    }
+}
+
+impl fmt::Display for DurationSetting {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DurationSetting::Seconds(seconds) => write!(formatter, "{seconds}"),
+            DurationSetting::Text(text) => write!(formatter, "\"{text}\""),
+        }
+    }
+}
+
+impl CommandDefinition {
+    /// What the command runs, whether a command line, an inline script,
+    /// or a script file
+    pub fn command_text(&self) -> String {
+        if let Some(file) = &self.file {
+            return file.clone();
+        }
+        if let Some(script) = &self.script {
+            return script.trim_end().to_string();
+        }
+        self.command.to_string()
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct RootCommand {
     pub name: String,
-    pub command: String,
     pub description: String,
-    pub args: Option<HashMap<String, String>>,
+    pub args: Option<Arguments>,
+    pub env: Option<Environment>,
+    pub cwd: Option<String>,
 }
 
 impl RootCommand {
@@ -22,24 +371,66 @@ impl RootCommand {
         RootCommand {
             name: name.to_string(),
             args: command.args.clone(),
-            command: command.command.to_string(),
+            env: command.env.clone(),
+            cwd: command.cwd.clone(),
             description: command.description.to_string(),
         }
     }
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
 pub struct RepoKitConfig {
     pub project: String,
-    pub thirdParty: Vec<RepoKitCommand>,
+    #[serde(default)]
+    pub third_party: Vec<RepoKitCommand>,
+    #[serde(default)]
     pub commands: HashMap<String, CommandDefinition>,
+    #[serde(default)]
+    pub auto_run_suggestions: bool,
+    #[serde(default)]
+    pub owners: IndexMap<String, OwnerDetails>,
+    #[serde(default)]
+    pub history: HistorySettings,
+    pub shell: Option<ShellSetting>,
+    /// How the configuration file was parsed
+    #[serde(skip)]
+    pub strategy: ParseStrategy,
 }
 
 #[derive(Debug, Deserialize, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HistorySettings {
+    pub enabled: bool,
+    pub redact: Vec<String>,
+    pub max_entries: usize,
+}
+
+impl Default for HistorySettings {
+    fn default() -> Self {
+        HistorySettings {
+            enabled: true,
+            redact: Vec::new(),
+            max_entries: 1000,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepoKitCommand {
     pub name: String,
-    pub owner: String,
+    #[serde(default)]
+    pub owner: Owners,
+    #[serde(default)]
     pub location: String,
     pub description: String,
+    #[serde(default)]
     pub commands: HashMap<String, CommandDefinition>,
+    pub env: Option<Environment>,
+    #[serde(rename = "envFile")]
+    pub env_file: Option<EnvironmentFiles>,
+    pub cwd: Option<String>,
+    pub shell: Option<ShellSetting>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
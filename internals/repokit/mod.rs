@@ -1,2 +1,4 @@
+pub mod exit_codes;
 pub mod interfaces;
+pub mod options;
 pub mod repokit;
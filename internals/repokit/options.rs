@@ -0,0 +1,52 @@
+use std::path::{PathBuf, absolute};
+
+use crate::output::output::OutputFormat;
+
+/// Options accepted by repokit itself. These must precede the
+/// command being invoked, so they never collide with its arguments
+#[derive(Debug, Default, Clone)]
+pub struct RepoKitOptions {
+    pub env_files: Vec<PathBuf>,
+    pub format: Option<OutputFormat>,
+    pub dry_run: bool,
+}
+
+impl RepoKitOptions {
+    /// Consumes the leading options from argv, returning them along
+    /// with the remaining tokens
+    pub fn parse(argv: &[String]) -> Result<(RepoKitOptions, Vec<String>), String> {
+        let mut options = RepoKitOptions::default();
+        let mut index = 0;
+        while let Some(token) = argv.get(index) {
+            if token == "--dry-run" {
+                options.dry_run = true;
+                index += 1;
+                continue;
+            }
+            let (option, inline) = match token.split_once('=') {
+                Some((option, value)) => (option, Some(value.to_string())),
+                None => (token.as_str(), None),
+            };
+            if !matches!(option, "--env-file" | "--format") {
+                break;
+            }
+            let value = match inline {
+                Some(value) => value,
+                None => {
+                    index += 1;
+                    argv.get(index)
+                        .cloned()
+                        .ok_or(format!("The option {option} requires a value"))?
+                }
+            };
+            match option {
+                "--format" => options.format = Some(OutputFormat::parse(&value)?),
+                _ => options
+                    .env_files
+                    .push(absolute(&value).unwrap_or(PathBuf::from(value))),
+            }
+            index += 1;
+        }
+        Ok((options, argv[index..].to_vec()))
+    }
+}
@@ -1,43 +1,107 @@
+use indexmap::IndexMap;
 use std::{
     collections::HashMap,
     env::args,
-    path::Path,
+    path::{Path, PathBuf},
     process::{self},
+    time::Instant,
 };
 
 use crate::{
+    configuration::configuration::Configuration,
+    dotenv::dotenv::DotEnv,
     executables::{
         intenal_executable::InternalExecutable, internal_executable_definition::RepoKitScope,
     },
-    executor::executor::Executor,
-    internal_commands::help::Help,
+    executor::{
+        execution_plan::ExecutionPlan,
+        interpolation::{Interpolation, InterpolationContext},
+        retry_policy::RetryPolicy,
+        shell::Shell,
+    },
+    history::history::History,
+    internal_commands::{
+        help::Help,
+        pick_command::{PICK_ALIAS, PickCommand},
+        shell_completions::{COMPLETION_ENTRY, ShellCompletions},
+    },
     logger::logger::Logger,
-    repokit::interfaces::{RepoKitCommand, RepoKitConfig},
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{CommandDefinition, CommandLine, RepoKitCommand, RepoKitConfig, RootCommand},
+        options::RepoKitOptions,
+    },
+    suggestions::suggestions::Suggestions,
+    validations::argument_validations::{ArgumentValidations, ParsedArguments},
     validations::command_validations::CommandValidations,
+    validations::environment_validations::EnvironmentValidations,
 };
 
 pub struct RepoKit {
     pub scope: RepoKitScope,
+    argv: Vec<String>,
 }
 
 impl RepoKit {
     pub fn new(root: String, configuration: RepoKitConfig) -> RepoKit {
         Logger::set_name(&configuration.project);
+        let tokens: Vec<String> = args().skip(1).collect();
+        let (options, argv) = match RepoKitOptions::parse(&tokens) {
+            Ok(parsed) => parsed,
+            Err(message) => Logger::exit_with_code(&message, ExitCodes::USAGE),
+        };
+        if options.format.is_some() {
+            Logger::diagnostics_to_stderr();
+            colored::control::set_override(false);
+        }
         RepoKit {
             scope: RepoKitScope {
                 root,
                 configuration,
+                options,
             },
+            argv,
+        }
+    }
+
+    /// Creates a kit for dispatching from within an internal command,
+    /// keeping the options the outer invocation was given
+    pub fn from_scope(scope: &RepoKitScope) -> RepoKit {
+        RepoKit {
+            scope: scope.clone(),
+            argv: Vec::new(),
         }
     }
 
-    pub fn invoke(&self) {
+    pub fn invoke(&self) -> i32 {
         let (command, args) = self.parse();
+        if command == COMPLETION_ENTRY {
+            return self.complete(&args);
+        }
+        if command == PICK_ALIAS {
+            return self.dispatch(PickCommand::new(&self.scope).definition.name, args);
+        }
+        self.dispatch(command, args)
+    }
+
+    pub fn dispatch(&self, command: String, args: Vec<String>) -> i32 {
         let validator = CommandValidations::from(self);
         let internals = validator.collect_and_validate_internals();
         if internals.contains_key(&command) {
             let interface = internals.get(&command).expect("exists");
-            return interface.run(args, &internals);
+            if self.scope.options.dry_run {
+                Logger::info(
+                    format!(
+                        "{} is an internal command. It runs within repokit rather than a shell",
+                        Logger::blue_bright(&command)
+                    )
+                    .as_str(),
+                );
+                return ExitCodes::SUCCESS;
+            }
+            return self.record(&[&command], &args, || {
+                interface.run(args.clone(), &internals)
+            });
         }
         if self.scope.configuration.commands.contains_key(&command) {
             let root_script = self
@@ -46,10 +110,29 @@ impl RepoKit {
                 .commands
                 .get(&command)
                 .expect("exists");
-            return Executor::with_stdio(
-                format!("{} {}", root_script.command, &args.join(" ")),
-                |cmd| cmd.current_dir(Path::new(&self.scope.root)),
-            );
+            let package_dir = Path::new(&self.scope.root);
+            let help = || Help::log_root_command(&RootCommand::from(&command, root_script));
+            return self.record(&[&command], &args, || {
+                let working_dir = match self.working_directory(root_script, None, package_dir) {
+                    Ok(working_dir) => working_dir,
+                    Err(message) => {
+                        Logger::error(&message);
+                        return ExitCodes::FAILURE;
+                    }
+                };
+                let arguments =
+                    match ArgumentValidations::validate(&root_script.args, &args, &working_dir) {
+                        Ok(arguments) => arguments,
+                        Err(message) => return self.invalid_arguments(&message, &command, help),
+                    };
+                let context = InterpolationContext {
+                    root: &self.scope.root,
+                    package_dir,
+                    working_dir: &working_dir,
+                    command_name: &command,
+                };
+                self.execute(root_script, None, &arguments, &context, &command, help)
+            });
         }
         let externals = validator.collect_and_validate_externals();
         CommandValidations::detect_collisions_between_internals_and_externals(
@@ -58,34 +141,68 @@ impl RepoKit {
         if externals.contains_key(&command) {
             let interface = externals.get(&command).expect("exists");
             if args.is_empty() {
-                return self.log_external_command(interface);
+                self.log_external_command(interface);
+                return ExitCodes::SUCCESS;
             }
             let sub_command = &args[0];
             if interface.commands.contains_key(sub_command) {
                 let script = interface.commands.get(sub_command).expect("exists");
-                let working_dir = Path::new(&interface.location).parent().expect("exists");
-                return Executor::with_stdio(
-                    format!("{} {}", &script.command, &args[1..].join(" ")),
-                    |cmd| cmd.current_dir(working_dir),
-                );
+                let package_dir = Path::new(&interface.location).parent().expect("exists");
+                let name = format!("{} {}", interface.name, sub_command);
+                let help = || {
+                    Help::log_external_subcommands(
+                        &HashMap::from([(sub_command.clone(), script.clone())]),
+                        3,
+                    )
+                };
+                return self.record(&[&command, sub_command], &args[1..], || {
+                    let working_dir =
+                        match self.working_directory(script, Some(interface), package_dir) {
+                            Ok(working_dir) => working_dir,
+                            Err(message) => {
+                                Logger::error(&message);
+                                return ExitCodes::FAILURE;
+                            }
+                        };
+                    let arguments =
+                        match ArgumentValidations::validate(&script.args, &args[1..], &working_dir)
+                        {
+                            Ok(arguments) => arguments,
+                            Err(message) => return self.invalid_arguments(&message, &name, help),
+                        };
+                    let context = InterpolationContext {
+                        root: &self.scope.root,
+                        package_dir,
+                        working_dir: &working_dir,
+                        command_name: sub_command,
+                    };
+                    self.execute(script, Some(interface), &arguments, &context, &name, help)
+                });
             }
-            return self.subcommand_not_found(interface, sub_command);
+            return self.subcommand_not_found(interface, sub_command, &args[1..]);
         }
-        self.command_not_found(&command, &internals, &externals)
+        self.command_not_found(&command, &args, &internals, &externals)
     }
 
     fn parse(&self) -> (String, Vec<String>) {
-        let argv: Vec<String> = args().collect();
-        if argv.len() < 2 {
+        let Some((command, args)) = self.argv.split_first() else {
             let (internals, externals) = self.collect_and_validate();
             Help::list_all(&self.scope.configuration.commands, &internals, &externals);
             process::exit(0);
-        }
-        let command = &argv[1];
-        let args = &(&argv)[2..];
+        };
         (command.clone(), args.to_vec())
     }
 
+    fn complete(&self, words: &[String]) -> i32 {
+        Logger::diagnostics_to_stderr();
+        let (internals, externals) = self.collect_and_validate();
+        for candidate in ShellCompletions::new(&self.scope).complete(words, &internals, &externals)
+        {
+            println!("{candidate}");
+        }
+        ExitCodes::SUCCESS
+    }
+
     fn collect_and_validate(
         &self,
     ) -> (
@@ -104,10 +221,28 @@ impl RepoKit {
     fn command_not_found(
         &self,
         command: &str,
+        args: &[String],
         internals: &HashMap<String, Box<dyn InternalExecutable>>,
         externals: &HashMap<String, RepoKitCommand>,
-    ) {
-        Help::list_all(&self.scope.configuration.commands, internals, externals);
+    ) -> i32 {
+        let mut candidates: Vec<(String, Vec<String>)> = internals
+            .keys()
+            .chain(self.scope.configuration.commands.keys())
+            .chain(externals.keys())
+            .map(|name| (name.clone(), vec![name.clone()]))
+            .collect();
+        for external in externals.values() {
+            for sub_command in external.commands.keys() {
+                candidates.push((
+                    sub_command.clone(),
+                    vec![external.name.clone(), sub_command.clone()],
+                ));
+            }
+        }
+        let suggestions = Suggestions::rank(command, candidates);
+        if suggestions.is_empty() {
+            Help::list_all(&self.scope.configuration.commands, internals, externals);
+        }
         Logger::info(
             format!(
                 "I'm not aware of a command named {}",
@@ -115,9 +250,225 @@ impl RepoKit {
             )
             .as_str(),
         );
+        self.suggest(&suggestions, args)
+            .unwrap_or(ExitCodes::COMMAND_NOT_FOUND)
+    }
+
+    /// Logs suggestions and, when enabled, runs the only suggestion
+    /// once the user confirms it
+    fn suggest(&self, suggestions: &[Vec<String>], args: &[String]) -> Option<i32> {
+        if suggestions.is_empty() {
+            return None;
+        }
+        Suggestions::log(suggestions);
+        if !self.scope.configuration.auto_run_suggestions || suggestions.len() != 1 {
+            return None;
+        }
+        let suggestion = &suggestions[0];
+        if !Suggestions::confirm(suggestion) {
+            return None;
+        }
+        let (command, rest) = suggestion.split_first()?;
+        Some(self.dispatch(command.clone(), [rest, args].concat()))
+    }
+
+    /// Runs a command, appending the invocation to the history. Dry runs
+    /// are never recorded
+    fn record(&self, command: &[&str], args: &[String], run: impl FnOnce() -> i32) -> i32 {
+        let started = Instant::now();
+        let code = run();
+        if self.scope.options.dry_run {
+            return code;
+        }
+        History::new(&self.scope.root, &self.scope.configuration.history).append(
+            command,
+            args,
+            started.elapsed(),
+            code,
+        );
+        code
+    }
+
+    fn execute(
+        &self,
+        definition: &CommandDefinition,
+        package: Option<&RepoKitCommand>,
+        arguments: &ParsedArguments,
+        context: &InterpolationContext,
+        name: &str,
+        help: impl Fn(),
+    ) -> i32 {
+        let files = match self.environment_files(definition, package, context) {
+            Ok(files) => files,
+            Err(message) => {
+                Logger::error(&message);
+                return ExitCodes::USAGE;
+            }
+        };
+        let mut variables = match self.load_environment_files(&files) {
+            Ok(variables) => variables,
+            Err(message) => {
+                Logger::error(&message);
+                return ExitCodes::FAILURE;
+            }
+        };
+        let declarations =
+            EnvironmentValidations::merge(&package.and_then(|x| x.env.clone()), &definition.env);
+        match EnvironmentValidations::validate(&declarations, &variables) {
+            Ok(defaults) => variables.extend(defaults),
+            Err(message) => return self.invalid_arguments(&message, name, help),
+        };
+        let shell = Shell::resolve(&[
+            definition.shell.as_ref(),
+            package.and_then(|x| x.shell.as_ref()),
+            self.scope.configuration.shell.as_ref(),
+        ]);
+        let shell = match shell {
+            Ok(shell) => shell,
+            Err(message) => {
+                Logger::error(&message);
+                return ExitCodes::FAILURE;
+            }
+        };
+        let (command, args, script) = if let Some(file) = &definition.file {
+            let path = Interpolation::path(file, context.root, context.package_dir);
+            let path = path.to_string_lossy().to_string();
+            (CommandLine::Argv(vec![path]), arguments.argv.clone(), None)
+        } else if let Some(script) = &definition.script {
+            let (interpreter, script) = match &definition.interpreter {
+                Some(interpreter) => (
+                    interpreter.split_whitespace().map(String::from).collect(),
+                    script.clone(),
+                ),
+                None => (shell.interpreter(), shell.script(script)),
+            };
+            (
+                CommandLine::Argv(interpreter),
+                arguments.argv.clone(),
+                Some(script),
+            )
+        } else {
+            match Interpolation::resolve(&definition.command, arguments, context) {
+                Ok((command, args)) => (command, args, None),
+                Err(message) => return self.invalid_arguments(&message, name, help),
+            }
+        };
+        let retry = match RetryPolicy::from(definition) {
+            Ok(retry) => retry,
+            Err(message) => {
+                Logger::error(&message);
+                return ExitCodes::FAILURE;
+            }
+        };
+        let plan = ExecutionPlan {
+            command,
+            args,
+            shell,
+            script,
+            retry,
+            cwd: context.working_dir.to_path_buf(),
+            env: variables,
+            manifest: match package {
+                Some(package) => PathBuf::from(&package.location),
+                None => Configuration::path(&self.scope.root),
+            },
+            key: context.command_name.to_string(),
+        };
+        if self.scope.options.dry_run {
+            plan.log(name);
+            return ExitCodes::SUCCESS;
+        }
+        plan.run()
+    }
+
+    /// Resolves the command's working directory, falling back to the
+    /// package's and then the directory of its definition
+    fn working_directory(
+        &self,
+        definition: &CommandDefinition,
+        package: Option<&RepoKitCommand>,
+        package_dir: &Path,
+    ) -> Result<PathBuf, String> {
+        let Some(cwd) = definition
+            .cwd
+            .as_ref()
+            .or(package.and_then(|x| x.cwd.as_ref()))
+        else {
+            return Ok(package_dir.to_path_buf());
+        };
+        let path = Interpolation::path(cwd, &self.scope.root, package_dir);
+        match path.canonicalize() {
+            Ok(resolved) if resolved.is_dir() => Ok(resolved),
+            _ => Err(format!(
+                "The working directory {} does not exist",
+                Logger::blue_bright(&path.to_string_lossy())
+            )),
+        }
     }
 
-    fn subcommand_not_found(&self, command: &RepoKitCommand, sub_command: &str) {
+    /// Resolves the env files declared by the package, then the command.
+    /// Files ending in ? are optional and skipped when they don't exist
+    fn environment_files(
+        &self,
+        definition: &CommandDefinition,
+        package: Option<&RepoKitCommand>,
+        context: &InterpolationContext,
+    ) -> Result<Vec<PathBuf>, String> {
+        let declared = package
+            .and_then(|x| x.env_file.as_ref())
+            .into_iter()
+            .chain(definition.env_file.as_ref())
+            .flat_map(|files| files.paths());
+        let mut files = Vec::new();
+        for declaration in declared {
+            let (path, optional) = match declaration.trim().strip_suffix('?') {
+                Some(path) => (path, true),
+                None => (declaration.trim(), false),
+            };
+            let path = context.package_dir.join(path);
+            if path.exists() {
+                files.push(path);
+            } else if !optional {
+                return Err(format!(
+                    "The env file {} does not exist. To make it optional, end its path with ?",
+                    Logger::blue_bright(&path.to_string_lossy())
+                ));
+            }
+        }
+        Ok(files)
+    }
+
+    /// Layers the declared env files, then those provided using
+    /// --env-file. Variables already present in the process environment
+    /// are never overridden
+    fn load_environment_files(
+        &self,
+        files: &[PathBuf],
+    ) -> Result<IndexMap<String, String>, String> {
+        let mut variables = IndexMap::new();
+        for path in files {
+            DotEnv::read(path, &mut variables)?;
+        }
+        for path in &self.scope.options.env_files {
+            DotEnv::read(path, &mut variables)?;
+        }
+        Ok(DotEnv::without_process_environment(variables))
+    }
+
+    fn invalid_arguments(&self, message: &str, name: &str, help: impl Fn()) -> i32 {
+        Logger::error(message);
+        Logger::info(format!("Here's how to use {}\n", Logger::blue_bright(name)).as_str());
+        help();
+        println!();
+        ExitCodes::USAGE
+    }
+
+    fn subcommand_not_found(
+        &self,
+        command: &RepoKitCommand,
+        sub_command: &str,
+        args: &[String],
+    ) -> i32 {
         Logger::info(
             format!(
                 "The command {} was not found on {}",
@@ -126,6 +477,17 @@ impl RepoKit {
             )
             .as_str(),
         );
+        let candidates = command
+            .commands
+            .keys()
+            .map(|name| (name.clone(), vec![command.name.clone(), name.clone()]))
+            .collect();
+        let suggestions = Suggestions::rank(sub_command, candidates);
+        if !suggestions.is_empty() {
+            return self
+                .suggest(&suggestions, args)
+                .unwrap_or(ExitCodes::COMMAND_NOT_FOUND);
+        }
         Logger::info(
             format!(
                 "Here are the commands that belong to {}",
@@ -134,6 +496,7 @@ impl RepoKit {
             .as_str(),
         );
         Help::log_external_subcommands(&command.commands, 3);
+        ExitCodes::COMMAND_NOT_FOUND
     }
 
     fn log_external_command(&self, command: &RepoKitCommand) {
@@ -0,0 +1,404 @@
+use regex::{Regex, RegexBuilder};
+
+use crate::search::search_document::{Field, SearchDocument};
+
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Text(String),
+    Regex(Regex),
+}
+
+#[derive(Debug, Clone)]
+pub struct Term {
+    pub field: Option<Field>,
+    pub matcher: Matcher,
+}
+
+#[derive(Debug, Clone)]
+pub enum Query {
+    Term(Term),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+    Not(Box<Query>),
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+static KEYWORDS: [&str; 3] = ["AND", "OR", "NOT"];
+
+impl Matcher {
+    /// Returns the byte ranges within the text that this matcher matches
+    pub fn find(&self, text: &str) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::Regex(regex) => regex
+                .find_iter(text)
+                .filter(|x| !x.is_empty())
+                .map(|x| (x.start(), x.end()))
+                .collect(),
+            Matcher::Text(needle) => {
+                let haystack = text.to_lowercase();
+                if needle.is_empty() || haystack.len() != text.len() {
+                    return Vec::new();
+                }
+                haystack
+                    .match_indices(needle.as_str())
+                    .map(|(start, x)| (start, start + x.len()))
+                    .collect()
+            }
+        }
+    }
+
+    fn is_match(&self, text: &str) -> bool {
+        match self {
+            Matcher::Regex(regex) => regex.is_match(text),
+            Matcher::Text(needle) => text.to_lowercase().contains(needle.as_str()),
+        }
+    }
+
+    fn is_exact(&self, text: &str) -> bool {
+        match self {
+            Matcher::Regex(_) => false,
+            Matcher::Text(needle) => text.to_lowercase() == *needle,
+        }
+    }
+}
+
+impl Query {
+    /// Builds a query from command line arguments. Arguments the shell
+    /// received as a single quoted string are treated as phrases
+    pub fn from_args(args: &[String]) -> Result<Query, String> {
+        let source: Vec<String> = args
+            .iter()
+            .map(|arg| {
+                let phrase = arg.contains(char::is_whitespace)
+                    && !arg.contains(['"', '/', '(', ')'])
+                    && !arg.split_whitespace().any(|x| KEYWORDS.contains(&x));
+                if !phrase {
+                    return arg.clone();
+                }
+                match Query::qualifier(arg) {
+                    Some((prefix, value)) => format!("{prefix}:\"{value}\""),
+                    None => format!("\"{arg}\""),
+                }
+            })
+            .collect();
+        Query::parse(&source.join(" "))
+    }
+
+    pub fn parse(source: &str) -> Result<Query, String> {
+        let tokens = Query::tokenize(source)?;
+        if tokens.is_empty() {
+            return Err("Please specify a search string to query with".to_string());
+        }
+        let mut position = 0;
+        let query = Query::parse_or(&tokens, &mut position)?;
+        if position < tokens.len() {
+            return Err("The search query contains an unmatched )".to_string());
+        }
+        Ok(query)
+    }
+
+    /// Returns a relevance score when the document satisfies the query
+    pub fn score(&self, document: &SearchDocument) -> Option<u32> {
+        match self {
+            Query::Term(term) => Query::score_term(term, document),
+            Query::And(left, right) => Some(left.score(document)? + right.score(document)?),
+            Query::Or(left, right) => match (left.score(document), right.score(document)) {
+                (None, None) => None,
+                (left, right) => Some(left.unwrap_or(0) + right.unwrap_or(0)),
+            },
+            Query::Not(query) => match query.score(document) {
+                Some(_) => None,
+                None => Some(0),
+            },
+        }
+    }
+
+    /// Returns the matchers whose matches should be highlighted
+    pub fn highlights(&self) -> Vec<&Matcher> {
+        match self {
+            Query::Term(term) => vec![&term.matcher],
+            Query::And(left, right) | Query::Or(left, right) => {
+                [left.highlights(), right.highlights()].concat()
+            }
+            Query::Not(_) => Vec::new(),
+        }
+    }
+
+    fn score_term(term: &Term, document: &SearchDocument) -> Option<u32> {
+        document
+            .fields
+            .iter()
+            .filter(|(field, _)| term.field.is_none_or(|x| x == *field))
+            .filter(|(_, value)| term.matcher.is_match(value))
+            .map(|(field, value)| {
+                let weight = field.weight();
+                if term.matcher.is_exact(value) {
+                    return weight * 2;
+                }
+                weight
+            })
+            .max()
+    }
+
+    fn parse_or(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+        let mut query = Query::parse_and(tokens, position)?;
+        while let Some(Token::Or) = tokens.get(*position) {
+            *position += 1;
+            let right = Query::parse_and(tokens, position)?;
+            query = Query::Or(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_and(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+        let mut query = Query::parse_unary(tokens, position)?;
+        loop {
+            match tokens.get(*position) {
+                Some(Token::And) => *position += 1,
+                Some(Token::Open | Token::Not | Token::Term(_)) => {}
+                _ => break,
+            }
+            let right = Query::parse_unary(tokens, position)?;
+            query = Query::And(Box::new(query), Box::new(right));
+        }
+        Ok(query)
+    }
+
+    fn parse_unary(tokens: &[Token], position: &mut usize) -> Result<Query, String> {
+        let token = tokens.get(*position).cloned();
+        *position += 1;
+        match token {
+            Some(Token::Not) => Ok(Query::Not(Box::new(Query::parse_unary(tokens, position)?))),
+            Some(Token::Open) => {
+                let query = Query::parse_or(tokens, position)?;
+                match tokens.get(*position) {
+                    Some(Token::Close) => {
+                        *position += 1;
+                        Ok(query)
+                    }
+                    _ => Err("The search query contains an unmatched (".to_string()),
+                }
+            }
+            Some(Token::Term(term)) => Ok(Query::Term(term)),
+            Some(Token::Close) => Err("The search query contains an unmatched )".to_string()),
+            Some(Token::And | Token::Or) | None => {
+                Err("The search query ends with an incomplete expression".to_string())
+            }
+        }
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+        let characters: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut index = 0;
+        while index < characters.len() {
+            let character = characters[index];
+            if character.is_whitespace() {
+                index += 1;
+                continue;
+            }
+            if character == '(' || character == ')' {
+                tokens.push(match character {
+                    '(' => Token::Open,
+                    _ => Token::Close,
+                });
+                index += 1;
+                continue;
+            }
+            let start = index;
+            let mut quote: Option<char> = None;
+            while index < characters.len() {
+                let current = characters[index];
+                match quote {
+                    Some(closing) if current == '\\' && closing == '/' => index += 1,
+                    Some(closing) if current == closing => quote = None,
+                    Some(_) => {}
+                    None if current == '"' => quote = Some('"'),
+                    None if current == '/' && Query::starts_value(&characters[start..index]) => {
+                        quote = Some('/')
+                    }
+                    None if current.is_whitespace() || current == '(' || current == ')' => break,
+                    None => {}
+                }
+                index += 1;
+            }
+            if let Some(closing) = quote {
+                return Err(format!("The search query is missing a closing {closing}"));
+            }
+            let word: String = characters[start..index.min(characters.len())]
+                .iter()
+                .collect();
+            tokens.push(match word.as_str() {
+                "AND" => Token::And,
+                "OR" => Token::Or,
+                "NOT" => Token::Not,
+                _ => Token::Term(Query::term(&word)?),
+            });
+        }
+        Ok(tokens)
+    }
+
+    /// Whether a / at this point begins a regular expression rather
+    /// than appearing within a path
+    fn starts_value(preceding: &[char]) -> bool {
+        let preceding: String = preceding.iter().collect();
+        preceding.is_empty()
+            || Query::qualifier(&preceding).is_some_and(|(_, value)| value.is_empty())
+    }
+
+    fn qualifier(word: &str) -> Option<(&str, &str)> {
+        let (prefix, value) = word.split_once(':')?;
+        Field::from_qualifier(prefix)?;
+        Some((prefix, value))
+    }
+
+    fn term(word: &str) -> Result<Term, String> {
+        let (field, value) = match Query::qualifier(word) {
+            Some((prefix, value)) => (Field::from_qualifier(prefix), value),
+            None => (None, word),
+        };
+        let is_regex = value.len() > 1 && value.starts_with('/') && value.ends_with('/');
+        if is_regex {
+            let pattern = &value[1..value.len() - 1];
+            let regex = RegexBuilder::new(pattern)
+                .case_insensitive(true)
+                .build()
+                .map_err(|_| format!("The regular expression /{pattern}/ is invalid"))?;
+            return Ok(Term {
+                field,
+                matcher: Matcher::Regex(regex),
+            });
+        }
+        let text = value
+            .strip_prefix('"')
+            .and_then(|x| x.strip_suffix('"'))
+            .unwrap_or(value);
+        Ok(Term {
+            field,
+            matcher: Matcher::Text(text.to_lowercase()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Matcher, Query};
+    use crate::search::search_document::{Field, SearchDocument, SearchSource};
+
+    /// Renders a query's structure, so that precedence can be compared
+    fn render(query: &Query) -> String {
+        match query {
+            Query::Term(term) => {
+                let value = match &term.matcher {
+                    Matcher::Text(text) => format!("\"{text}\""),
+                    Matcher::Regex(regex) => format!("/{}/", regex.as_str()),
+                };
+                match term.field {
+                    Some(field) => format!("{field:?}:{value}"),
+                    None => value,
+                }
+            }
+            Query::And(left, right) => format!("(and {} {})", render(left), render(right)),
+            Query::Or(left, right) => format!("(or {} {})", render(left), render(right)),
+            Query::Not(query) => format!("(not {})", render(query)),
+        }
+    }
+
+    fn parse(source: &str) -> String {
+        render(&Query::parse(source).unwrap())
+    }
+
+    fn document(fields: &[(Field, &str)]) -> SearchDocument {
+        SearchDocument {
+            source: SearchSource::Registered,
+            name: String::new(),
+            invocation: Vec::new(),
+            description: String::new(),
+            command: None,
+            owner: None,
+            location: None,
+            args: Vec::new(),
+            tags: Vec::new(),
+            arguments: None,
+            fields: fields
+                .iter()
+                .map(|(field, value)| (*field, value.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn binds_not_tighter_than_and_tighter_than_or() {
+        assert_eq!(parse("a OR b c"), "(or \"a\" (and \"b\" \"c\"))");
+        assert_eq!(parse("a b OR c"), "(or (and \"a\" \"b\") \"c\")");
+        assert_eq!(parse("NOT a AND b"), "(and (not \"a\") \"b\")");
+        assert_eq!(
+            parse("(a OR b) AND NOT c"),
+            "(and (or \"a\" \"b\") (not \"c\"))"
+        );
+    }
+
+    #[test]
+    fn reads_phrases_and_qualifiers() {
+        assert_eq!(
+            parse("\"run tests\" tag:ci"),
+            "(and \"run tests\" Tag:\"ci\")"
+        );
+        assert_eq!(parse("owner:\"Go Team\""), "Owner:\"go team\"");
+        assert_eq!(parse("path:src/api"), "Path:\"src/api\"");
+    }
+
+    #[test]
+    fn treats_quoted_arguments_as_phrases() {
+        let args = ["deploy staging".to_string(), "name:build all".to_string()];
+        let query = Query::from_args(&args).unwrap();
+        assert_eq!(
+            render(&query),
+            "(and \"deploy staging\" Name:\"build all\")"
+        );
+    }
+
+    #[test]
+    fn reads_regular_expressions() {
+        assert_eq!(parse("/^dep(loy)?$/"), "/^dep(loy)?$/");
+        assert_eq!(parse("cmd:/a\\/b/"), "Command:/a\\/b/");
+        assert!(Query::parse("/[unclosed/").is_err());
+        assert!(Query::parse("/open").is_err());
+    }
+
+    #[test]
+    fn rejects_malformed_queries() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("(a OR b").is_err());
+        assert!(Query::parse("a)").is_err());
+        assert!(Query::parse("a OR").is_err());
+        assert!(Query::parse("\"open").is_err());
+    }
+
+    #[test]
+    fn scores_matching_documents() {
+        let deploy = document(&[(Field::Name, "deploy"), (Field::Tag, "release")]);
+        let build = document(&[
+            (Field::Name, "build"),
+            (Field::Description, "prepares a deploy"),
+        ]);
+        let query = Query::parse("deploy").unwrap();
+        assert_eq!(query.score(&deploy), Some(20));
+        assert_eq!(query.score(&build), Some(2));
+        let query = Query::parse("name:deploy OR tag:release").unwrap();
+        assert_eq!(query.score(&deploy), Some(32));
+        assert_eq!(query.score(&build), None);
+        let query = Query::parse("NOT /^dep/").unwrap();
+        assert_eq!(query.score(&deploy), None);
+        assert_eq!(query.score(&build), Some(0));
+    }
+}
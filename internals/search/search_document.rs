@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    configuration::configuration::Configuration,
+    executables::{
+        intenal_executable::InternalExecutable,
+        internal_executable_definition::{InternalExecutableDefinition, RepoKitScope},
+    },
+    repokit::interfaces::{ArgumentDefinition, Arguments, CommandDefinition, RepoKitCommand},
+    validations::argument_validations::ArgumentValidations,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Field {
+    Name,
+    Owner,
+    Path,
+    Command,
+    Argument,
+    Tag,
+    Description,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSource {
+    Internal,
+    Root,
+    Registered,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchDocument {
+    pub source: SearchSource,
+    pub name: String,
+    pub invocation: Vec<String>,
+    pub description: String,
+    pub command: Option<String>,
+    pub owner: Option<String>,
+    pub location: Option<String>,
+    pub args: Vec<(String, String)>,
+    pub tags: Vec<String>,
+    pub arguments: Option<Arguments>,
+    pub fields: Vec<(Field, String)>,
+}
+
+impl Field {
+    pub fn from_qualifier(qualifier: &str) -> Option<Field> {
+        match qualifier {
+            "name" => Some(Field::Name),
+            "owner" => Some(Field::Owner),
+            "path" => Some(Field::Path),
+            "cmd" => Some(Field::Command),
+            "arg" => Some(Field::Argument),
+            "tag" => Some(Field::Tag),
+            _ => None,
+        }
+    }
+
+    pub fn weight(&self) -> u32 {
+        match self {
+            Field::Name => 10,
+            Field::Tag => 6,
+            Field::Command => 5,
+            Field::Owner => 4,
+            Field::Argument => 4,
+            Field::Path => 3,
+            Field::Description => 2,
+        }
+    }
+}
+
+impl SearchSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchSource::Internal => "internal",
+            SearchSource::Root => "root",
+            SearchSource::Registered => "registered",
+        }
+    }
+}
+
+impl SearchDocument {
+    /// Collects a document for every internal, root, and registered command
+    pub fn collect(
+        scope: &RepoKitScope,
+        internals: &HashMap<String, Box<dyn InternalExecutable>>,
+        externals: &HashMap<String, RepoKitCommand>,
+    ) -> Vec<SearchDocument> {
+        let mut documents: Vec<SearchDocument> = internals
+            .values()
+            .map(|internal| SearchDocument::internal(internal.get_definition()))
+            .collect();
+        let configuration = format!("/{}", Configuration::file_name(&scope.root));
+        for (name, definition) in &scope.configuration.commands {
+            documents.push(SearchDocument::root(name, definition, &configuration));
+        }
+        for external in externals.values() {
+            let location = external.location.replace(scope.root.as_str(), "");
+            for (name, definition) in &external.commands {
+                documents.push(SearchDocument::registered(
+                    external, name, definition, &location,
+                ));
+            }
+        }
+        documents
+    }
+
+    pub fn internal(definition: &InternalExecutableDefinition) -> SearchDocument {
+        let mut args: Vec<(String, String)> = definition
+            .args
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        args.sort();
+        SearchDocument {
+            source: SearchSource::Internal,
+            name: definition.name.clone(),
+            invocation: vec![definition.name.clone()],
+            description: definition.description.clone(),
+            command: None,
+            owner: None,
+            location: None,
+            args,
+            tags: Vec::new(),
+            arguments: None,
+            fields: Vec::new(),
+        }
+        .index(&[])
+    }
+
+    pub fn root(name: &str, definition: &CommandDefinition, location: &str) -> SearchDocument {
+        SearchDocument {
+            source: SearchSource::Root,
+            name: name.to_string(),
+            invocation: vec![name.to_string()],
+            description: definition.description.clone(),
+            command: Some(definition.command_text()),
+            owner: None,
+            location: Some(location.to_string()),
+            args: SearchDocument::args(definition),
+            tags: definition.tags.clone(),
+            arguments: definition.args.clone(),
+            fields: Vec::new(),
+        }
+        .index(&[])
+    }
+
+    pub fn registered(
+        package: &RepoKitCommand,
+        name: &str,
+        definition: &CommandDefinition,
+        location: &str,
+    ) -> SearchDocument {
+        SearchDocument {
+            source: SearchSource::Registered,
+            name: format!("{} {}", package.name, name),
+            invocation: vec![package.name.clone(), name.to_string()],
+            description: definition.description.clone(),
+            command: Some(definition.command_text()),
+            owner: Some(package.owner.to_string()).filter(|x| !x.is_empty()),
+            location: Some(location.to_string()),
+            args: SearchDocument::args(definition),
+            tags: [package.tags.clone(), definition.tags.clone()].concat(),
+            arguments: definition.args.clone(),
+            fields: Vec::new(),
+        }
+        .index(
+            &[
+                vec![
+                    (Field::Name, package.name.clone()),
+                    (Field::Name, name.to_string()),
+                    (Field::Description, package.description.clone()),
+                ],
+                package
+                    .owner
+                    .names()
+                    .into_iter()
+                    .map(|x| (Field::Owner, x.to_string()))
+                    .collect(),
+            ]
+            .concat(),
+        )
+    }
+
+    fn args(definition: &CommandDefinition) -> Vec<(String, String)> {
+        let Some(args) = &definition.args else {
+            return Vec::new();
+        };
+        args.iter()
+            .map(|(key, definition)| {
+                let description = match definition {
+                    ArgumentDefinition::Description(description) => description.clone(),
+                    ArgumentDefinition::Schema(schema) => schema.description.clone(),
+                };
+                (
+                    ArgumentValidations::names(key, definition).join(" | "),
+                    description,
+                )
+            })
+            .collect()
+    }
+
+    fn index(mut self, additional: &[(Field, String)]) -> SearchDocument {
+        let mut fields = vec![
+            (Field::Name, self.name.clone()),
+            (Field::Description, self.description.clone()),
+        ];
+        fields.extend(self.command.iter().map(|x| (Field::Command, x.clone())));
+        fields.extend(self.owner.iter().map(|x| (Field::Owner, x.clone())));
+        fields.extend(self.location.iter().map(|x| (Field::Path, x.clone())));
+        for (names, description) in &self.args {
+            fields.push((Field::Argument, names.clone()));
+            fields.push((Field::Argument, description.clone()));
+        }
+        fields.extend(self.tags.iter().map(|x| (Field::Tag, x.clone())));
+        fields.extend(additional.iter().cloned());
+        self.fields = fields;
+        self
+    }
+}
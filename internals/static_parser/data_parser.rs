@@ -0,0 +1,74 @@
+use std::{fs, path::Path};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    logger::logger::Logger,
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{RepoKitCommand, RepoKitConfig},
+    },
+    static_parser::static_parser::ParseStrategy,
+};
+
+pub static DATA_MANIFESTS: [&str; 4] = [
+    "repokit.toml",
+    "repokit.yaml",
+    "repokit.yml",
+    "repokit.json",
+];
+
+pub struct DataParser;
+
+impl DataParser {
+    pub fn is_manifest(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| DATA_MANIFESTS.contains(&name))
+    }
+
+    pub fn strategy(path: &Path) -> Option<ParseStrategy> {
+        match path.extension()?.to_str()? {
+            "toml" => Some(ParseStrategy::Toml),
+            "yaml" | "yml" => Some(ParseStrategy::Yaml),
+            "json" => Some(ParseStrategy::Json),
+            _ => None,
+        }
+    }
+
+    pub fn parse_command(path: &Path) -> Result<RepoKitCommand, String> {
+        let mut command: RepoKitCommand = DataParser::deserialize(path)?;
+        command.location = path.to_str().expect("path").to_string();
+        Ok(command)
+    }
+
+    pub fn parse_configuration(path: &Path) -> Result<RepoKitConfig, String> {
+        let mut config: RepoKitConfig = DataParser::deserialize(path)?;
+        for command in &mut config.third_party {
+            command.location = path.to_str().expect("path").to_string();
+        }
+        Ok(config)
+    }
+
+    pub fn exit_on_failure(path: &Path, error: &str) -> ! {
+        Logger::error(
+            format!(
+                "I encountered an error while parsing {}",
+                Logger::blue_bright(path.to_str().expect("path"))
+            )
+            .as_str(),
+        );
+        eprintln!("\n{error}\n");
+        Logger::exit_with_code("Please correct it and try again", ExitCodes::PARSE_FAILURE)
+    }
+
+    fn deserialize<T: DeserializeOwned>(path: &Path) -> Result<T, String> {
+        let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+        match DataParser::strategy(path) {
+            Some(ParseStrategy::Toml) => toml::from_str(&contents).map_err(|e| e.to_string()),
+            Some(ParseStrategy::Yaml) => serde_yaml::from_str(&contents).map_err(|e| e.to_string()),
+            Some(ParseStrategy::Json) => serde_json::from_str(&contents).map_err(|e| e.to_string()),
+            _ => Err("unsupported file format".to_string()),
+        }
+    }
+}
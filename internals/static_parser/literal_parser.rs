@@ -0,0 +1,474 @@
+use serde_json::{Map, Number, Value};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenKind {
+    Identifier(String),
+    String(String),
+    Number(String),
+    Template(String),
+    Interpolation,
+    Punctuator(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub line: usize,
+}
+
+#[derive(Debug)]
+pub enum LiteralError {
+    Computed(usize),
+    Syntax(usize),
+    Incomplete,
+}
+
+impl LiteralError {
+    pub fn describe(&self) -> String {
+        match self {
+            LiteralError::Computed(line) => format!("it contains computed values on line {line}"),
+            LiteralError::Syntax(line) => format!("it could not be read statically on line {line}"),
+            LiteralError::Incomplete => "its definition could not be read statically".to_string(),
+        }
+    }
+}
+
+pub struct LiteralParser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl LiteralParser {
+    pub fn new(source: &str) -> Result<LiteralParser, LiteralError> {
+        Ok(LiteralParser {
+            tokens: LiteralParser::tokenize(source)?,
+            position: 0,
+        })
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn seek(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn expect(&mut self, punctuator: &str) -> Result<(), LiteralError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Punctuator(value),
+                ..
+            }) if value == punctuator => Ok(()),
+            Some(token) => Err(LiteralError::Syntax(token.line)),
+            None => Err(LiteralError::Syntax(self.line())),
+        }
+    }
+
+    pub fn value(&mut self) -> Result<Value, LiteralError> {
+        let Some(token) = self.next() else {
+            return Err(LiteralError::Syntax(self.line()));
+        };
+        match token.kind {
+            TokenKind::String(value) | TokenKind::Template(value) => Ok(Value::String(value)),
+            TokenKind::Interpolation => Err(LiteralError::Computed(token.line)),
+            TokenKind::Number(value) => LiteralParser::number(&value)
+                .map(Value::Number)
+                .ok_or(LiteralError::Syntax(token.line)),
+            TokenKind::Identifier(identifier) => match identifier.as_str() {
+                "true" => Ok(Value::Bool(true)),
+                "false" => Ok(Value::Bool(false)),
+                "null" | "undefined" => Ok(Value::Null),
+                "new" => self.instance(),
+                _ => Err(LiteralError::Computed(token.line)),
+            },
+            TokenKind::Punctuator(punctuator) => match punctuator.as_str() {
+                "{" => self.object(),
+                "[" => self.array(),
+                "-" => match self.value()? {
+                    Value::Number(number) => LiteralParser::number(&format!("-{number}"))
+                        .map(Value::Number)
+                        .ok_or(LiteralError::Syntax(token.line)),
+                    _ => Err(LiteralError::Computed(token.line)),
+                },
+                _ => Err(LiteralError::Computed(token.line)),
+            },
+        }
+    }
+
+    fn instance(&mut self) -> Result<Value, LiteralError> {
+        let line = self.line();
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Identifier(class),
+                ..
+            }) if class == "RepoKitCommand" => {}
+            _ => return Err(LiteralError::Computed(line)),
+        }
+        self.expect("(")?;
+        let value = self.value()?;
+        self.skip(",");
+        self.expect(")")?;
+        Ok(value)
+    }
+
+    fn object(&mut self) -> Result<Value, LiteralError> {
+        let mut map = Map::new();
+        loop {
+            if self.skip("}") {
+                return Ok(Value::Object(map));
+            }
+            let Some(token) = self.next() else {
+                return Err(LiteralError::Syntax(self.line()));
+            };
+            let key = match token.kind {
+                TokenKind::Identifier(key)
+                | TokenKind::String(key)
+                | TokenKind::Number(key)
+                | TokenKind::Template(key) => key,
+                _ => return Err(LiteralError::Computed(token.line)),
+            };
+            if !self.skip(":") {
+                return Err(LiteralError::Computed(token.line));
+            }
+            let value = self.value()?;
+            if !matches!(self.peek_punctuator(), Some("," | "}")) {
+                return Err(LiteralError::Computed(self.line()));
+            }
+            if !value.is_null() {
+                map.insert(key, value);
+            }
+            if !self.skip(",") {
+                self.expect("}")?;
+                return Ok(Value::Object(map));
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, LiteralError> {
+        let mut list = Vec::new();
+        loop {
+            if self.skip("]") {
+                return Ok(Value::Array(list));
+            }
+            list.push(self.value()?);
+            if !matches!(self.peek_punctuator(), Some("," | "]")) {
+                return Err(LiteralError::Computed(self.line()));
+            }
+            if !self.skip(",") {
+                self.expect("]")?;
+                return Ok(Value::Array(list));
+            }
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn skip(&mut self, punctuator: &str) -> bool {
+        if self.peek_punctuator() == Some(punctuator) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn peek_punctuator(&self) -> Option<&str> {
+        match self.tokens.get(self.position) {
+            Some(Token {
+                kind: TokenKind::Punctuator(value),
+                ..
+            }) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .or(self.tokens.last())
+            .map(|token| token.line)
+            .unwrap_or(1)
+    }
+
+    fn number(literal: &str) -> Option<Number> {
+        let cleaned = literal.replace('_', "");
+        let (negative, digits) = match cleaned.strip_prefix('-') {
+            Some(digits) => (true, digits.to_string()),
+            None => (false, cleaned.clone()),
+        };
+        let radix = match digits.get(..2) {
+            Some("0x" | "0X") => Some(16),
+            Some("0o" | "0O") => Some(8),
+            Some("0b" | "0B") => Some(2),
+            _ => None,
+        };
+        if let Some(radix) = radix {
+            let value = i64::from_str_radix(&digits[2..], radix).ok()?;
+            return Some(Number::from(if negative { -value } else { value }));
+        }
+        if let Ok(value) = cleaned.parse::<i64>() {
+            return Some(Number::from(value));
+        }
+        Number::from_f64(cleaned.parse::<f64>().ok()?)
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<Token>, LiteralError> {
+        let characters: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut index = 0;
+        let mut line = 1;
+        while index < characters.len() {
+            let character = characters[index];
+            if character == '\n' {
+                line += 1;
+                index += 1;
+                continue;
+            }
+            if character.is_whitespace() {
+                index += 1;
+                continue;
+            }
+            if character == '/' && characters.get(index + 1) == Some(&'/') {
+                while index < characters.len() && characters[index] != '\n' {
+                    index += 1;
+                }
+                continue;
+            }
+            if character == '/' && characters.get(index + 1) == Some(&'*') {
+                index += 2;
+                while index < characters.len()
+                    && !(characters[index] == '*' && characters.get(index + 1) == Some(&'/'))
+                {
+                    if characters[index] == '\n' {
+                        line += 1;
+                    }
+                    index += 1;
+                }
+                index += 2;
+                continue;
+            }
+            let start_line = line;
+            if character == '"' || character == '\'' || character == '`' {
+                let (value, interpolated, end, lines) =
+                    LiteralParser::string(&characters, index, character)
+                        .ok_or(LiteralError::Syntax(start_line))?;
+                index = end;
+                line += lines;
+                let kind = match (character, interpolated) {
+                    ('`', true) => TokenKind::Interpolation,
+                    ('`', false) => TokenKind::Template(value),
+                    _ => TokenKind::String(value),
+                };
+                tokens.push(Token {
+                    kind,
+                    line: start_line,
+                });
+                continue;
+            }
+            if character.is_ascii_digit()
+                || (character == '.'
+                    && characters
+                        .get(index + 1)
+                        .is_some_and(|next| next.is_ascii_digit()))
+            {
+                let start = index;
+                while index < characters.len()
+                    && (characters[index].is_ascii_alphanumeric()
+                        || characters[index] == '.'
+                        || characters[index] == '_'
+                        || ((characters[index] == '+' || characters[index] == '-')
+                            && matches!(characters[index - 1], 'e' | 'E')))
+                {
+                    index += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Number(characters[start..index].iter().collect()),
+                    line,
+                });
+                continue;
+            }
+            if character.is_alphabetic() || character == '_' || character == '$' {
+                let start = index;
+                while index < characters.len()
+                    && (characters[index].is_alphanumeric()
+                        || characters[index] == '_'
+                        || characters[index] == '$')
+                {
+                    index += 1;
+                }
+                tokens.push(Token {
+                    kind: TokenKind::Identifier(characters[start..index].iter().collect()),
+                    line,
+                });
+                continue;
+            }
+            let punctuator = if characters[index..].starts_with(&['.', '.', '.']) {
+                "...".to_string()
+            } else {
+                character.to_string()
+            };
+            index += punctuator.len();
+            tokens.push(Token {
+                kind: TokenKind::Punctuator(punctuator),
+                line,
+            });
+        }
+        Ok(tokens)
+    }
+
+    fn string(
+        characters: &[char],
+        start: usize,
+        quote: char,
+    ) -> Option<(String, bool, usize, usize)> {
+        let mut value = String::new();
+        let mut interpolated = false;
+        let mut lines = 0;
+        let mut index = start + 1;
+        while index < characters.len() {
+            let character = characters[index];
+            if character == quote {
+                return Some((value, interpolated, index + 1, lines));
+            }
+            if character == '\n' {
+                if quote != '`' {
+                    return None;
+                }
+                lines += 1;
+            }
+            if quote == '`' && character == '$' && characters.get(index + 1) == Some(&'{') {
+                interpolated = true;
+            }
+            if character == '\\' {
+                index += 1;
+                let escaped = *characters.get(index)?;
+                match escaped {
+                    'n' => value.push('\n'),
+                    't' => value.push('\t'),
+                    'r' => value.push('\r'),
+                    'b' => value.push('\u{8}'),
+                    'f' => value.push('\u{c}'),
+                    'v' => value.push('\u{b}'),
+                    '0' => value.push('\0'),
+                    '\n' => lines += 1,
+                    'x' => {
+                        let code: String = characters.get(index + 1..index + 3)?.iter().collect();
+                        value.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                        index += 2;
+                    }
+                    'u' => {
+                        let (code, length) = if characters.get(index + 1) == Some(&'{') {
+                            let end = characters[index..].iter().position(|c| *c == '}')? + index;
+                            (
+                                characters[index + 2..end].iter().collect::<String>(),
+                                end - index,
+                            )
+                        } else {
+                            (characters.get(index + 1..index + 5)?.iter().collect(), 4)
+                        };
+                        value.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                        index += length;
+                    }
+                    other => value.push(other),
+                }
+                index += 1;
+                continue;
+            }
+            value.push(character);
+            index += 1;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{LiteralError, LiteralParser, TokenKind};
+
+    fn parse(source: &str) -> Result<serde_json::Value, LiteralError> {
+        LiteralParser::new(source)?.value()
+    }
+
+    #[test]
+    fn reads_escape_sequences() {
+        let value = parse(
+            r#"["a\"b", 'it\'s', "tab\there", "\x41B\u{43}", "line\
+break"]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!(["a\"b", "it's", "tab\there", "ABC", "linebreak"])
+        );
+    }
+
+    #[test]
+    fn reads_template_literals_without_interpolation() {
+        let value = parse("{ script: `echo one\necho two` }").unwrap();
+        assert_eq!(value, json!({ "script": "echo one\necho two" }));
+    }
+
+    #[test]
+    fn tokenizes_interpolated_templates() {
+        let parser = LiteralParser::new("`echo ${name}`").unwrap();
+        assert_eq!(parser.tokens()[0].kind, TokenKind::Interpolation);
+    }
+
+    #[test]
+    fn reads_objects_arrays_and_numbers() {
+        let value = parse(
+            "{ name: 'api', 'quoted-key': true, count: 0x10, ratio: -1.5, list: [1_000, null,], skipped: undefined, }",
+        )
+        .unwrap();
+        assert_eq!(
+            value,
+            json!({ "name": "api", "quoted-key": true, "count": 16, "ratio": -1.5, "list": [1000, null] })
+        );
+    }
+
+    #[test]
+    fn skips_comments() {
+        let value = parse("{\n  // a comment\n  a: 1, /* another */ b: 2\n}").unwrap();
+        assert_eq!(value, json!({ "a": 1, "b": 2 }));
+    }
+
+    #[test]
+    fn falls_back_on_computed_values() {
+        for source in [
+            "{ name: NAME }",
+            "{ name: `api-${env}` }",
+            "{ name: 'a' + 'b' }",
+            "{ ...shared }",
+            "{ run: build() }",
+        ] {
+            assert!(
+                matches!(parse(source), Err(LiteralError::Computed(_))),
+                "{source} should require evaluation"
+            );
+        }
+    }
+
+    #[test]
+    fn reports_the_line_of_computed_values() {
+        let error = parse("{\n  a: 1,\n  b: value,\n}").unwrap_err();
+        assert!(matches!(error, LiteralError::Computed(3)));
+    }
+
+    #[test]
+    fn rejects_unterminated_strings() {
+        assert!(matches!(
+            LiteralParser::new("{ a: 'open }"),
+            Err(LiteralError::Syntax(1))
+        ));
+    }
+}
@@ -0,0 +1,3 @@
+pub mod data_parser;
+pub mod literal_parser;
+pub mod static_parser;
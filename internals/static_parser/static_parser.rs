@@ -0,0 +1,181 @@
+use std::{fmt, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    repokit::interfaces::{RepoKitCommand, RepoKitConfig},
+    static_parser::literal_parser::{LiteralError, LiteralParser, Token, TokenKind},
+};
+
+static CLASSES: [&str; 2] = ["RepoKitCommand", "RepoKitConfig"];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum ParseStrategy {
+    #[default]
+    #[serde(rename = "static")]
+    Static,
+    #[serde(rename = "tsx")]
+    Typescript,
+    #[serde(rename = "toml")]
+    Toml,
+    #[serde(rename = "yaml")]
+    Yaml,
+    #[serde(rename = "json")]
+    Json,
+}
+
+impl fmt::Display for ParseStrategy {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseStrategy::Static => write!(formatter, "static"),
+            ParseStrategy::Typescript => write!(formatter, "tsx"),
+            ParseStrategy::Toml => write!(formatter, "toml"),
+            ParseStrategy::Yaml => write!(formatter, "yaml"),
+            ParseStrategy::Json => write!(formatter, "json"),
+        }
+    }
+}
+
+struct Instance {
+    class: String,
+    value: Value,
+}
+
+pub struct StaticParser;
+
+impl StaticParser {
+    pub fn parse_commands(path: &Path) -> Result<Vec<RepoKitCommand>, LiteralError> {
+        let location = path.to_str().expect("path");
+        StaticParser::instances(path)?
+            .into_iter()
+            .filter(|instance| instance.class == CLASSES[0])
+            .map(|instance| StaticParser::command(instance.value, location))
+            .collect()
+    }
+
+    pub fn parse_configuration(path: &Path) -> Result<RepoKitConfig, LiteralError> {
+        let location = path.to_str().expect("path");
+        let Some(instance) = StaticParser::instances(path)?
+            .into_iter()
+            .find(|instance| instance.class == CLASSES[1])
+        else {
+            return Err(LiteralError::Incomplete);
+        };
+        let Value::Object(mut map) = instance.value else {
+            return Err(LiteralError::Incomplete);
+        };
+        let mut third_party = Vec::new();
+        if let Some(Value::Array(commands)) = map.remove("thirdParty") {
+            for command in commands {
+                third_party.push(StaticParser::located(command, location)?);
+            }
+        }
+        map.insert("thirdParty".to_string(), Value::Array(third_party));
+        map.entry("commands")
+            .or_insert_with(|| Value::Object(Map::new()));
+        serde_json::from_value(Value::Object(map)).map_err(|_| LiteralError::Incomplete)
+    }
+
+    fn command(value: Value, location: &str) -> Result<RepoKitCommand, LiteralError> {
+        serde_json::from_value(StaticParser::located(value, location)?)
+            .map_err(|_| LiteralError::Incomplete)
+    }
+
+    fn located(value: Value, location: &str) -> Result<Value, LiteralError> {
+        let Value::Object(mut map) = value else {
+            return Err(LiteralError::Incomplete);
+        };
+        map.insert("location".to_string(), Value::String(location.to_string()));
+        Ok(Value::Object(map))
+    }
+
+    fn instances(path: &Path) -> Result<Vec<Instance>, LiteralError> {
+        let source = fs::read_to_string(path).map_err(|_| LiteralError::Incomplete)?;
+        let mut parser = LiteralParser::new(&source)?;
+        let tokens: Vec<Token> = parser.tokens().to_vec();
+        let mut instances = Vec::new();
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        let mut index = 0;
+        while index < tokens.len() {
+            let Some((class, start)) = StaticParser::exported_instance(&tokens, index) else {
+                index += 1;
+                continue;
+            };
+            parser.seek(start);
+            let value = parser.value()?;
+            parser.expect(")")?;
+            spans.push((index, parser.position()));
+            instances.push(Instance { class, value });
+            index = parser.position();
+        }
+        for (index, token) in tokens.iter().enumerate() {
+            let constructed = StaticParser::identifier(&tokens, index) == Some("new")
+                && StaticParser::identifier(&tokens, index + 1)
+                    .is_some_and(|class| CLASSES.contains(&class));
+            if constructed
+                && !spans
+                    .iter()
+                    .any(|(start, end)| *start <= index && index < *end)
+            {
+                return Err(LiteralError::Computed(token.line));
+            }
+        }
+        Ok(instances)
+    }
+
+    fn exported_instance(tokens: &[Token], index: usize) -> Option<(String, usize)> {
+        if StaticParser::identifier(tokens, index) != Some("export") {
+            return None;
+        }
+        let mut cursor = index + 1;
+        if StaticParser::identifier(tokens, cursor) == Some("default") {
+            cursor += 1;
+        } else {
+            if !matches!(
+                StaticParser::identifier(tokens, cursor),
+                Some("const" | "let" | "var")
+            ) {
+                return None;
+            }
+            StaticParser::identifier(tokens, cursor + 1)?;
+            cursor += 2;
+            if StaticParser::punctuator(tokens, cursor) == Some(":") {
+                StaticParser::identifier(tokens, cursor + 1)?;
+                cursor += 2;
+            }
+            if StaticParser::punctuator(tokens, cursor) != Some("=") {
+                return None;
+            }
+            cursor += 1;
+        }
+        if StaticParser::identifier(tokens, cursor) != Some("new") {
+            return None;
+        }
+        let class = StaticParser::identifier(tokens, cursor + 1)?;
+        if !CLASSES.contains(&class) || StaticParser::punctuator(tokens, cursor + 2) != Some("(") {
+            return None;
+        }
+        Some((class.to_string(), cursor + 3))
+    }
+
+    fn identifier(tokens: &[Token], index: usize) -> Option<&str> {
+        match tokens.get(index) {
+            Some(Token {
+                kind: TokenKind::Identifier(value),
+                ..
+            }) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+
+    fn punctuator(tokens: &[Token], index: usize) -> Option<&str> {
+        match tokens.get(index) {
+            Some(Token {
+                kind: TokenKind::Punctuator(value),
+                ..
+            }) => Some(value.as_str()),
+            _ => None,
+        }
+    }
+}
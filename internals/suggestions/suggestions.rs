@@ -0,0 +1,96 @@
+use std::io::{IsTerminal, Write, stdin, stdout};
+
+use crate::logger::logger::Logger;
+
+pub struct Suggestions;
+
+pub static MAX_SUGGESTIONS: usize = 3;
+
+impl Suggestions {
+    /// Ranks candidates by their similarity to the query. Each candidate
+    /// pairs the name to match with the tokens that invoke it
+    pub fn rank(query: &str, candidates: Vec<(String, Vec<String>)>) -> Vec<Vec<String>> {
+        let query = query.to_lowercase();
+        let length = query.chars().count();
+        let threshold = (length / 3).max(1);
+        let mut ranked: Vec<(usize, String, Vec<String>)> = candidates
+            .into_iter()
+            .filter_map(|(name, invocation)| {
+                let lowercase = name.to_lowercase();
+                let distance = Suggestions::distance(&query, &lowercase);
+                let contains = length >= 3 && lowercase.contains(&query);
+                if distance > threshold && !contains {
+                    return None;
+                }
+                Some((distance.min(threshold), name, invocation))
+            })
+            .collect();
+        ranked.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        let mut suggestions: Vec<Vec<String>> = Vec::new();
+        for (_, _, invocation) in ranked {
+            if !suggestions.contains(&invocation) {
+                suggestions.push(invocation);
+            }
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+
+    pub fn log(suggestions: &[Vec<String>]) {
+        Logger::info("Did you mean:");
+        for suggestion in suggestions {
+            println!(
+                "{}{}",
+                Logger::indent(Some(3)),
+                Logger::blue_bright(suggestion.join(" ").as_str())
+            );
+        }
+        println!();
+    }
+
+    /// Prompts for confirmation before running a suggestion. Declines
+    /// when repokit is not attached to an interactive terminal
+    pub fn confirm(suggestion: &[String]) -> bool {
+        if !stdin().is_terminal() {
+            return false;
+        }
+        print!(
+            "{}Run {}? {} ",
+            Logger::indent(Some(3)),
+            Logger::blue_bright(suggestion.join(" ").as_str()),
+            Logger::gray("[y/N]")
+        );
+        let _ = stdout().flush();
+        let mut answer = String::new();
+        if stdin().read_line(&mut answer).is_err() {
+            return false;
+        }
+        matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+    }
+
+    /// The optimal string alignment distance between two strings
+    fn distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut matrix = vec![vec![0; b.len() + 1]; a.len() + 1];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[0] = i;
+        }
+        for (j, cell) in matrix[0].iter_mut().enumerate() {
+            *cell = j;
+        }
+        for i in 1..=a.len() {
+            for j in 1..=b.len() {
+                let cost = usize::from(a[i - 1] != b[j - 1]);
+                let mut value = (matrix[i - 1][j] + 1)
+                    .min(matrix[i][j - 1] + 1)
+                    .min(matrix[i - 1][j - 1] + cost);
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    value = value.min(matrix[i - 2][j - 2] + 1);
+                }
+                matrix[i][j] = value;
+            }
+        }
+        matrix[a.len()][b.len()]
+    }
+}
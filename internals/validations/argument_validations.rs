@@ -0,0 +1,391 @@
+use std::path::Path;
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::repokit::interfaces::{
+    ArgumentDefinition, ArgumentKind, ArgumentSchema, ArgumentType, Arguments,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ParsedArguments {
+    pub argv: Vec<String>,
+    pub named: IndexMap<String, Vec<String>>,
+    pub positionals: Vec<String>,
+    /// The argument each token of argv was parsed as, if any
+    pub claims: Vec<Option<String>>,
+    /// The position in argv of each positional
+    pub positional_indices: Vec<usize>,
+}
+
+struct Specification<'a> {
+    name: String,
+    names: Vec<String>,
+    kind: ArgumentKind,
+    schema: Option<&'a ArgumentSchema>,
+}
+
+pub struct ArgumentValidations;
+
+impl ArgumentValidations {
+    pub fn names(key: &str, definition: &ArgumentDefinition) -> Vec<String> {
+        let mut names: Vec<String> = key
+            .split('|')
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if let ArgumentDefinition::Schema(schema) = definition {
+            for alias in &schema.aliases {
+                if !names.contains(alias) {
+                    names.push(alias.clone());
+                }
+            }
+        }
+        names
+    }
+
+    pub fn kind(key: &str, definition: &ArgumentDefinition) -> ArgumentKind {
+        if let ArgumentDefinition::Schema(ArgumentSchema {
+            kind: Some(kind), ..
+        }) = definition
+        {
+            return *kind;
+        }
+        if key.trim_start().starts_with('-') {
+            return ArgumentKind::Flag;
+        }
+        ArgumentKind::Positional
+    }
+
+    pub fn canonical(key: &str) -> String {
+        key.split('|')
+            .next()
+            .unwrap_or(key)
+            .trim()
+            .trim_start_matches('-')
+            .trim_matches(|c| matches!(c, '<' | '>' | '[' | ']'))
+            .to_string()
+    }
+
+    pub fn default_value(schema: &ArgumentSchema) -> Option<String> {
+        match &schema.default {
+            None | Some(Value::Null) => None,
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(value) => Some(value.to_string()),
+        }
+    }
+
+    pub fn is_structured(args: &Option<Arguments>) -> bool {
+        args.as_ref().is_some_and(|args| {
+            args.values()
+                .any(|definition| matches!(definition, ArgumentDefinition::Schema(_)))
+        })
+    }
+
+    pub fn validate(
+        args: &Option<Arguments>,
+        argv: &[String],
+        working_directory: &Path,
+    ) -> Result<ParsedArguments, String> {
+        // Without a schema, arguments including any `--` are forwarded as
+        // they were given
+        let Some(args) = args
+            .as_ref()
+            .filter(|_| ArgumentValidations::is_structured(args))
+        else {
+            return Ok(ParsedArguments {
+                argv: argv.to_vec(),
+                positionals: argv.to_vec(),
+                claims: vec![None; argv.len()],
+                positional_indices: (0..argv.len()).collect(),
+                ..Default::default()
+            });
+        };
+        let separator = argv.iter().position(|x| x == "--").unwrap_or(argv.len());
+        let (options, verbatim) = argv.split_at(separator);
+        let verbatim = verbatim.get(1..).unwrap_or_default();
+        let mut parsed = ParsedArguments {
+            argv: options.to_vec(),
+            claims: vec![None; options.len()],
+            ..Default::default()
+        };
+        let strict = args
+            .values()
+            .all(|definition| matches!(definition, ArgumentDefinition::Schema(_)));
+        let specifications: Vec<Specification> = args
+            .iter()
+            .map(|(key, definition)| Specification {
+                name: ArgumentValidations::canonical(key),
+                names: ArgumentValidations::names(key, definition),
+                kind: ArgumentValidations::kind(key, definition),
+                schema: match definition {
+                    ArgumentDefinition::Schema(schema) => Some(schema),
+                    ArgumentDefinition::Description(_) => None,
+                },
+            })
+            .collect();
+        let flags: Vec<&Specification> = specifications
+            .iter()
+            .filter(|x| x.kind == ArgumentKind::Flag)
+            .collect();
+        let slots: Vec<&Specification> = specifications
+            .iter()
+            .filter(|x| x.kind == ArgumentKind::Positional)
+            .collect();
+        let mut slot = 0;
+        let mut index = 0;
+        while index < options.len() {
+            let token = &options[index];
+            if ArgumentValidations::is_flag(token) {
+                let (flag, inline) = match token.split_once('=') {
+                    Some((flag, value)) => (flag, Some(value.to_string())),
+                    None => (token.as_str(), None),
+                };
+                let Some(specification) = flags.iter().find(|x| x.names.iter().any(|n| n == flag))
+                else {
+                    if strict {
+                        return Err(format!("I don't recognize the option {flag}"));
+                    }
+                    index += 1;
+                    continue;
+                };
+                parsed.claims[index] = Some(specification.name.clone());
+                let Some(schema) = specification.schema else {
+                    parsed
+                        .named
+                        .entry(specification.name.clone())
+                        .or_default()
+                        .push(inline.unwrap_or_default());
+                    index += 1;
+                    continue;
+                };
+                let value = match (schema.value_type, inline) {
+                    (_, Some(value)) => value,
+                    (ArgumentType::Bool, None) => "true".to_string(),
+                    (_, None) => {
+                        index += 1;
+                        match options.get(index) {
+                            Some(value) => {
+                                parsed.claims[index] = Some(specification.name.clone());
+                                value.clone()
+                            }
+                            None => return Err(format!("The option {flag} requires a value")),
+                        }
+                    }
+                };
+                ArgumentValidations::check(flag, schema, &value, working_directory)?;
+                let values = parsed.named.entry(specification.name.clone()).or_default();
+                if !values.is_empty() && !schema.repeats {
+                    return Err(format!("The option {flag} can only be specified once"));
+                }
+                values.push(value);
+                index += 1;
+                continue;
+            }
+            parsed.positionals.push(token.clone());
+            parsed.positional_indices.push(index);
+            match slots.get(slot) {
+                Some(specification) => {
+                    parsed.claims[index] = Some(specification.name.clone());
+                    if let Some(schema) = specification.schema {
+                        ArgumentValidations::check(
+                            specification.names.first().unwrap_or(&specification.name),
+                            schema,
+                            token,
+                            working_directory,
+                        )?;
+                        if !schema.repeats {
+                            slot += 1;
+                        }
+                    } else {
+                        slot += 1;
+                    }
+                    parsed
+                        .named
+                        .entry(specification.name.clone())
+                        .or_default()
+                        .push(token.clone());
+                }
+                None if strict => {
+                    return Err(format!("I received an unexpected argument {token}"));
+                }
+                None => {}
+            }
+            index += 1;
+        }
+        let mut defaults: Vec<String> = Vec::new();
+        let mut default_claims: Vec<Option<String>> = Vec::new();
+        for specification in flags.iter().chain(slots.iter()) {
+            let Some(schema) = specification.schema else {
+                continue;
+            };
+            if parsed.named.contains_key(&specification.name) {
+                continue;
+            }
+            let display = specification.names.first().cloned().unwrap_or_default();
+            match ArgumentValidations::default_value(schema) {
+                Some(value) => {
+                    if specification.kind == ArgumentKind::Positional {
+                        parsed.positionals.push(value.clone());
+                        parsed
+                            .positional_indices
+                            .push(parsed.argv.len() + defaults.len());
+                        defaults.push(value.clone());
+                    } else if schema.value_type == ArgumentType::Bool {
+                        if value == "true" {
+                            defaults.push(display);
+                        }
+                    } else {
+                        defaults.push(display);
+                        defaults.push(value.clone());
+                    }
+                    parsed.named.insert(specification.name.clone(), vec![value]);
+                    default_claims.resize(defaults.len(), Some(specification.name.clone()));
+                }
+                None if schema.required => {
+                    return Err(match specification.kind {
+                        ArgumentKind::Flag => format!("The option {display} is required"),
+                        ArgumentKind::Positional => {
+                            format!("The argument {display} is required")
+                        }
+                    });
+                }
+                None => {}
+            }
+        }
+        parsed.argv.extend(defaults);
+        parsed.claims.extend(default_claims);
+        parsed
+            .positional_indices
+            .extend(parsed.argv.len()..parsed.argv.len() + verbatim.len());
+        parsed.argv.extend_from_slice(verbatim);
+        parsed.claims.resize(parsed.argv.len(), None);
+        parsed.positionals.extend_from_slice(verbatim);
+        Ok(parsed)
+    }
+
+    fn is_flag(token: &str) -> bool {
+        token.starts_with('-') && token.len() > 1 && token.parse::<f64>().is_err()
+    }
+
+    fn check(
+        name: &str,
+        schema: &ArgumentSchema,
+        value: &str,
+        working_directory: &Path,
+    ) -> Result<(), String> {
+        let valid = match schema.value_type {
+            ArgumentType::String => true,
+            ArgumentType::Int => value.parse::<i64>().is_ok(),
+            ArgumentType::Bool => {
+                matches!(value, "true" | "false" | "1" | "0" | "yes" | "no")
+            }
+            ArgumentType::Enum => schema.values.iter().any(|x| x == value),
+            ArgumentType::Path => working_directory.join(value).exists(),
+        };
+        if valid {
+            return Ok(());
+        }
+        let expectation = match schema.value_type {
+            ArgumentType::String => String::new(),
+            ArgumentType::Int => "an integer".to_string(),
+            ArgumentType::Bool => "true or false".to_string(),
+            ArgumentType::Enum => format!("one of {}", schema.values.join(" | ")),
+            ArgumentType::Path => "a path that exists".to_string(),
+        };
+        Err(format!(
+            "{name} expects {expectation} but received \"{value}\""
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::{ArgumentValidations, ParsedArguments};
+    use crate::repokit::interfaces::Arguments;
+
+    fn schema() -> Option<Arguments> {
+        serde_json::from_value(json!({
+            "--env": { "description": "Environment", "default": "dev" },
+            "--replicas": { "description": "Replicas", "type": "int" },
+            "--dry-run": { "description": "Dry run", "type": "bool" },
+            "--level": { "description": "Level", "type": "enum", "values": ["low", "high"] },
+            "region": { "description": "Region", "default": "us" }
+        }))
+        .ok()
+    }
+
+    fn validate(args: &Option<Arguments>, argv: &[&str]) -> Result<ParsedArguments, String> {
+        let argv: Vec<String> = argv.iter().map(|x| x.to_string()).collect();
+        ArgumentValidations::validate(args, &argv, Path::new("."))
+    }
+
+    #[test]
+    fn forwards_untyped_arguments_verbatim() {
+        let parsed = validate(&None, &["--nocapture", "--", "-x"]).unwrap();
+        assert_eq!(parsed.argv, ["--nocapture", "--", "-x"]);
+        assert_eq!(parsed.positionals, parsed.argv);
+    }
+
+    #[test]
+    fn stops_parsing_options_after_separator() {
+        let parsed = validate(&schema(), &["eu", "--", "--unknown", "-x"]).unwrap();
+        assert_eq!(parsed.argv, ["eu", "--env", "dev", "--unknown", "-x"]);
+        assert_eq!(parsed.positionals, ["eu", "--unknown", "-x"]);
+    }
+
+    #[test]
+    fn coerces_flags_and_values() {
+        let parsed =
+            validate(&schema(), &["--replicas=3", "--dry-run", "--level", "high"]).unwrap();
+        assert_eq!(parsed.named["replicas"], ["3"]);
+        assert_eq!(parsed.named["dry-run"], ["true"]);
+        assert_eq!(parsed.named["level"], ["high"]);
+    }
+
+    #[test]
+    fn rejects_values_of_the_wrong_type() {
+        assert!(validate(&schema(), &["--replicas", "three"]).is_err());
+        assert!(validate(&schema(), &["--level", "medium"]).is_err());
+        assert!(validate(&schema(), &["--dry-run=maybe"]).is_err());
+        assert!(validate(&schema(), &["--replicas"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options_in_strict_schemas() {
+        assert!(validate(&schema(), &["--unknown"]).is_err());
+        assert!(validate(&schema(), &["eu", "extra"]).is_err());
+    }
+
+    #[test]
+    fn applies_defaults() {
+        let parsed = validate(&schema(), &[]).unwrap();
+        assert_eq!(parsed.named["env"], ["dev"]);
+        assert_eq!(parsed.named["region"], ["us"]);
+        assert_eq!(parsed.positionals, ["us"]);
+        assert_eq!(parsed.argv, ["--env", "dev", "us"]);
+    }
+
+    #[test]
+    fn requires_required_arguments() {
+        let args: Option<Arguments> = serde_json::from_value(json!({
+            "--token": { "description": "Token", "required": true }
+        }))
+        .ok();
+        let error = validate(&args, &[]).unwrap_err();
+        assert_eq!(error, "The option --token is required");
+    }
+
+    #[test]
+    fn claims_tokens_for_their_arguments() {
+        let parsed = validate(&schema(), &["--replicas", "3", "eu"]).unwrap();
+        assert_eq!(parsed.claims[0].as_deref(), Some("replicas"));
+        assert_eq!(parsed.claims[1].as_deref(), Some("replicas"));
+        assert_eq!(parsed.claims[2].as_deref(), Some("region"));
+        assert_eq!(parsed.positional_indices, [2]);
+    }
+}
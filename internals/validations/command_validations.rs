@@ -1,15 +1,26 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fs::{self, Metadata},
+    path::Path,
+};
 
 use futures::executor;
 
 use crate::{
+    code_owners::code_owners::CodeOwners,
+    configuration::configuration::Configuration,
     executables::{
         intenal_executable::InternalExecutable, internal_executable_definition::RepoKitScope,
     },
+    executor::interpolation::Interpolation,
     external_commands::external_commands::ExternalCommands,
     internal_commands::internal_registry::InternalRegistry,
     logger::logger::Logger,
-    repokit::{interfaces::RepoKitCommand, repokit::RepoKit},
+    repokit::{
+        exit_codes::ExitCodes,
+        interfaces::{CommandDefinition, Owner, Owners, RepoKitCommand},
+        repokit::RepoKit,
+    },
 };
 
 pub struct CommandValidations {
@@ -38,8 +49,117 @@ impl CommandValidations {
     pub fn collect_and_validate_externals(&self) -> HashMap<String, RepoKitCommand> {
         let finder = ExternalCommands::new(&self.scope.root);
         let externals = executor::block_on(finder.find_all());
-        let all = [&externals[..], &self.scope.configuration.thirdParty[..]].concat();
-        self.detect_collisions_between_root_commands_and_externals(&all)
+        let all = [&externals[..], &self.scope.configuration.third_party[..]].concat();
+        let mut map = self.detect_collisions_between_root_commands_and_externals(&all);
+        for command in map.values() {
+            CommandValidations::validate_definitions(
+                &command.commands,
+                &self.scope.root,
+                &command.location,
+            );
+        }
+        self.resolve_owners(&mut map);
+        map
+    }
+
+    /// Exits when a command in a manifest defines nothing or more than one
+    /// thing to run, or references a script file that cannot be run
+    pub fn validate_definitions(
+        commands: &HashMap<String, CommandDefinition>,
+        root: &str,
+        location: &str,
+    ) {
+        let package_dir = Path::new(location)
+            .parent()
+            .filter(|x| !x.as_os_str().is_empty())
+            .unwrap_or(Path::new(root));
+        let mut names: Vec<&String> = commands.keys().collect();
+        names.sort();
+        for name in names {
+            let definition = &commands[name];
+            let result =
+                CommandValidations::validate_definition(definition).and_then(|_| match &definition
+                    .file
+                {
+                    Some(file) => CommandValidations::validate_script_file(&Interpolation::path(
+                        file,
+                        root,
+                        package_dir,
+                    )),
+                    None => Ok(()),
+                });
+            if let Err(message) = result {
+                Logger::info(
+                    format!(
+                        "I encountered an invalid command named {}. {message}",
+                        Logger::blue_bright(name)
+                    )
+                    .as_str(),
+                );
+                Logger::info("Here's where it's located:");
+                Logger::log_file_path(location);
+                Logger::exit_with_code("Please fix it", ExitCodes::PARSE_FAILURE);
+            }
+        }
+    }
+
+    /// Resolves references to the owner registry and falls back to
+    /// CODEOWNERS for packages that do not declare an owner
+    fn resolve_owners(&self, externals: &mut HashMap<String, RepoKitCommand>) {
+        let registry = &self.scope.configuration.owners;
+        let code_owners = CodeOwners::load(&self.scope.root);
+        for command in externals.values_mut() {
+            command.owner = command.owner.resolve(registry);
+            if !command.owner.is_empty() {
+                continue;
+            }
+            let path = command.location.replace(self.scope.root.as_str(), "");
+            if let Some(rule) = code_owners.as_ref().and_then(|x| x.rule_for(&path)) {
+                let owners = rule.owners.iter().map(|x| Owner::Name(x.clone()));
+                command.owner = Owners::Many(owners.collect());
+            }
+        }
+    }
+
+    /// Ensures a command defines exactly one of a command line, an inline
+    /// script, or a script file
+    fn validate_definition(definition: &CommandDefinition) -> Result<(), String> {
+        let defined = [
+            !definition.command.is_empty(),
+            definition.script.is_some(),
+            definition.file.is_some(),
+        ];
+        match defined.iter().filter(|x| **x).count() {
+            0 => Err("This command does not define anything to run".to_string()),
+            1 => Ok(()),
+            _ => Err(
+                "This command may only define one of a command, a script, or a file".to_string(),
+            ),
+        }
+    }
+
+    fn validate_script_file(path: &Path) -> Result<(), String> {
+        let display = Logger::blue_bright(&path.to_string_lossy()).to_string();
+        let Some(metadata) = fs::metadata(path).ok().filter(|x| x.is_file()) else {
+            return Err(format!("The script {display} does not exist"));
+        };
+        if !CommandValidations::is_executable(&metadata) {
+            return Err(format!(
+                "The script {display} is not executable. You can make it executable using chmod +x"
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    fn is_executable(metadata: &Metadata) -> bool {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    }
+
+    #[cfg(not(unix))]
+    fn is_executable(_: &Metadata) -> bool {
+        true
     }
 
     pub fn detect_collisions_between_internals_and_externals(
@@ -57,7 +177,7 @@ impl CommandValidations {
                 );
                 Logger::info("Here's where it's located:");
                 Logger::log_file_path(&command.location);
-                Logger::exit_with_info("Please rename it");
+                Logger::exit_with_code("Please rename it", ExitCodes::COLLISION);
             }
         }
     }
@@ -72,11 +192,11 @@ impl CommandValidations {
                     format!(
                         "I encountered a command named {} in your {} file that conflicts with one of my internals",
                         Logger::blue_bright(name),
-                        Logger::blue_bright("repokit.ts"),
+                        Logger::blue_bright(&Configuration::file_name(&self.scope.root)),
                     )
                     .as_str(),
                 );
-                Logger::exit_with_info("Please rename it");
+                Logger::exit_with_code("Please rename it", ExitCodes::COLLISION);
             }
         }
     }
@@ -108,13 +228,13 @@ impl CommandValidations {
         Logger::info(format!(
                 "I encountered a package command named {} that conflicts with a command in your {} file",
                 Logger::blue_bright(&command.name),
-                Logger::blue_bright("repokit.ts")
+                Logger::blue_bright(&Configuration::file_name(&self.scope.root))
             )
             .as_str(),
         );
         Logger::info("Here's where it's located:");
         Logger::log_file_path(&command.location);
-        Logger::exit_with_info("Please rename one of these");
+        Logger::exit_with_code("Please rename one of these", ExitCodes::COLLISION);
     }
 
     fn on_external_duplicate_collision(&self, command: &RepoKitCommand, collision_path: &str) {
@@ -136,6 +256,6 @@ impl CommandValidations {
             Logger::indent(None),
             Logger::blue_bright(&command.location)
         );
-        Logger::exit_with_info("Please rename one of these");
+        Logger::exit_with_code("Please rename one of these", ExitCodes::COLLISION);
     }
 }
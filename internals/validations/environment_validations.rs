@@ -0,0 +1,65 @@
+use std::env;
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::repokit::interfaces::{Environment, EnvironmentDefinition, EnvironmentSchema};
+
+pub struct EnvironmentValidations;
+
+impl EnvironmentValidations {
+    /// Merges package and command level declarations. Command level
+    /// declarations take precedence over their package's
+    pub fn merge(package: &Option<Environment>, command: &Option<Environment>) -> Environment {
+        let mut merged = package.clone().unwrap_or_default();
+        if let Some(command) = command {
+            for (name, definition) in command {
+                merged.insert(name.clone(), definition.clone());
+            }
+        }
+        merged
+    }
+
+    pub fn default_value(schema: &EnvironmentSchema) -> Option<String> {
+        match &schema.default {
+            None | Some(Value::Null) => None,
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(value) => Some(value.to_string()),
+        }
+    }
+
+    /// Returns the defaults to inject for each declared variable that
+    /// is not present in the current environment or the loaded variables
+    pub fn validate(
+        declarations: &Environment,
+        loaded: &IndexMap<String, String>,
+    ) -> Result<IndexMap<String, String>, String> {
+        let mut defaults = IndexMap::new();
+        for (name, definition) in declarations {
+            let EnvironmentDefinition::Schema(schema) = definition else {
+                continue;
+            };
+            let current = env::var(name).ok().or_else(|| loaded.get(name).cloned());
+            let value = match current {
+                Some(value) if !value.is_empty() => value,
+                _ => match EnvironmentValidations::default_value(schema) {
+                    Some(value) => {
+                        defaults.insert(name.clone(), value.clone());
+                        value
+                    }
+                    None if schema.required => {
+                        return Err(format!("The environment variable {name} is required"));
+                    }
+                    None => continue,
+                },
+            };
+            if !schema.values.is_empty() && !schema.values.contains(&value) {
+                return Err(format!(
+                    "{name} expects one of {} but received \"{value}\"",
+                    schema.values.join(" | ")
+                ));
+            }
+        }
+        Ok(defaults)
+    }
+}
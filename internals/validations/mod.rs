@@ -1 +1,3 @@
+pub mod argument_validations;
 pub mod command_validations;
+pub mod environment_validations;